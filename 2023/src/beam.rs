@@ -0,0 +1,257 @@
+use crate::aoc::Direction;
+use std::fmt::Display;
+
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Content {
+    #[default]
+    Empty,
+    VSplit,
+    HSplit,
+    FMirror,
+    BMirror,
+}
+
+impl Content {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Empty => ".",
+            Self::VSplit => "|",
+            Self::HSplit => "-",
+            Self::FMirror => "/",
+            Self::BMirror => "\\",
+        }
+    }
+
+    pub fn from_char(c: char) -> Self {
+        match c {
+            '.' => Self::Empty,
+            '|' => Self::VSplit,
+            '-' => Self::HSplit,
+            '/' => Self::FMirror,
+            '\\' => Self::BMirror,
+            _ => panic!("bad content"),
+        }
+    }
+
+    pub fn deflect(&self, direction: Direction) -> Vec<Direction> {
+        use Direction::*;
+        match (self, direction) {
+            (Self::FMirror, North) => vec![East],
+            (Self::FMirror, South) => vec![West],
+            (Self::FMirror, East) => vec![North],
+            (Self::FMirror, West) => vec![South],
+            (Self::BMirror, North) => vec![West],
+            (Self::BMirror, South) => vec![East],
+            (Self::BMirror, East) => vec![South],
+            (Self::BMirror, West) => vec![North],
+            (Self::HSplit, North | South) => vec![East, West],
+            (Self::VSplit, East | West) => vec![North, South],
+            _ => vec![direction],
+        }
+    }
+}
+
+fn bit(direction: Direction) -> u8 {
+    match direction {
+        Direction::North => 1,
+        Direction::South => 2,
+        Direction::East => 4,
+        Direction::West => 8,
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Beam {
+    pub location: (usize, usize),
+    pub direction: Direction,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Frame {
+    pub beams: Vec<Beam>,
+    pub seen: Vec<u8>,
+}
+
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Contraption {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<Content>,
+}
+
+impl Contraption {
+    pub fn new(content: &str) -> Self {
+        let lines = content.lines().collect::<Vec<_>>();
+        let height = lines.len();
+        let width = lines.first().map(|e| e.len()).unwrap_or(0);
+        let cells = lines
+            .iter()
+            .flat_map(|e| e.chars().map(Content::from_char))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Content {
+        self.cells[y * self.width + x]
+    }
+
+    fn step(&self, (x, y): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        match direction {
+            Direction::North if y > 0 => Some((x, y - 1)),
+            Direction::South if y + 1 < self.height => Some((x, y + 1)),
+            Direction::East if x + 1 < self.width => Some((x + 1, y)),
+            Direction::West if x > 0 => Some((x - 1, y)),
+            _ => None,
+        }
+    }
+
+    /// `observe` is called once per round with the beams just processed.
+    pub fn propagate<F>(&self, start: Beam, mut observe: F) -> Vec<u8>
+    where
+        F: FnMut(&[Beam], &[u8]),
+    {
+        let mut seen = vec![0_u8; self.cells.len()];
+        let mut beams = vec![start];
+        while !beams.is_empty() {
+            let mut next = vec![];
+            for beam in beams.iter() {
+                let index = beam.location.1 * self.width + beam.location.0;
+                if seen[index] & bit(beam.direction) != 0 {
+                    continue;
+                }
+                seen[index] |= bit(beam.direction);
+                for direction in self.get(beam.location).deflect(beam.direction) {
+                    if let Some(location) = self.step(beam.location, direction) {
+                        next.push(Beam {
+                            location,
+                            direction,
+                        });
+                    }
+                }
+            }
+            observe(&beams, &seen);
+            beams = next;
+        }
+        seen
+    }
+
+    pub fn energized(&self, start: Beam) -> usize {
+        self.propagate(start, |_, _| {})
+            .iter()
+            .filter(|&&e| e != 0)
+            .count()
+    }
+
+    pub fn record(&self, start: Beam) -> Vec<Frame> {
+        let mut frames = vec![];
+        self.propagate(start, |beams, seen| {
+            frames.push(Frame {
+                beams: beams.to_vec(),
+                seen: seen.to_vec(),
+            })
+        });
+        frames
+    }
+
+    pub fn entry_points(&self) -> Vec<Beam> {
+        let mut beams = vec![];
+        for y in 0..self.height {
+            beams.push(Beam {
+                location: (0, y),
+                direction: Direction::East,
+            });
+            beams.push(Beam {
+                location: (self.width - 1, y),
+                direction: Direction::West,
+            });
+        }
+        for x in 0..self.width {
+            beams.push(Beam {
+                location: (x, 0),
+                direction: Direction::South,
+            });
+            beams.push(Beam {
+                location: (x, self.height - 1),
+                direction: Direction::North,
+            });
+        }
+        beams
+    }
+
+    pub fn best_entry(&self) -> Option<(Beam, usize)> {
+        let entries = self.entry_points();
+        let threads = std::thread::available_parallelism().map_or(1, |e| e.get());
+        let chunk = entries.len().div_ceil(threads).max(1);
+        std::thread::scope(|scope| {
+            let workers = entries
+                .chunks(chunk)
+                .map(|beams| {
+                    scope.spawn(move || {
+                        beams
+                            .iter()
+                            .map(|&e| (e, self.energized(e)))
+                            .max_by_key(|e| e.1)
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .filter_map(|e| e.join().unwrap())
+                .max_by_key(|e| e.1)
+        })
+    }
+}
+
+impl Display for Contraption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            for content in row {
+                write!(f, "{}", content.label())?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deflect() {
+        assert_eq!(
+            vec![Direction::North],
+            Content::FMirror.deflect(Direction::East)
+        );
+        assert_eq!(
+            vec![Direction::East, Direction::West],
+            Content::HSplit.deflect(Direction::South)
+        );
+        assert_eq!(
+            vec![Direction::West],
+            Content::HSplit.deflect(Direction::West)
+        );
+    }
+
+    #[test]
+    fn test_loop() {
+        let contraption = Contraption::new("/.\\\n|..\n\\./");
+        assert_eq!(3, contraption.width);
+        assert_eq!("/.\\\n|..\n\\./\n", contraption.to_string());
+        let start = Beam {
+            location: (1, 0),
+            direction: Direction::East,
+        };
+        let seen = contraption.propagate(start, |_, _| {});
+        assert_eq!(0, seen[4]);
+        assert_eq!(bit(Direction::North), seen[0]);
+        assert_eq!(bit(Direction::East), seen[1]);
+        assert_eq!(8, contraption.energized(start));
+        assert_eq!(12, contraption.entry_points().len());
+    }
+}
@@ -1,338 +1,79 @@
 use aoc_2023::aoc::*;
-use futures::future::select_all;
 use itertools::Itertools;
-use tokio::spawn;
+use rayon::prelude::*;
 
-pub fn duplicate(
-    springs: String,
-    spec: Vec<usize>,
-    count: usize,
-    result: Option<u128>,
-) -> (String, Vec<usize>, Option<u128>) {
-    let spring_string = (0..count)
-        .map(|_| springs.clone())
-        .intersperse_with(|| "?".to_owned())
-        .fold("".to_owned(), |mut a, e| {
-            a.push_str(&e);
-            a
-        });
-
-    let summary = (0..count).flat_map(|_| spec.clone()).collect::<Vec<_>>();
-
-    (spring_string, summary, result)
-}
+type Record = (String, Vec<usize>, Option<u128>);
 
 fn input() -> &'static str {
     include_str!("input.txt")
 }
-fn partial() -> &'static str {
-    include_str!("partial.txt")
-}
-
-pub fn longest_run(input: &str) -> (usize, usize) {
-    let hashes = input
-        .chars()
-        .map(|e| if e == '?' { '#' } else { e })
-        .collect::<String>();
-    let longest_run = hashes
-        .split('.')
-        .fold("", |a, e| if a.len() > e.len() { a } else { e });
-    let position = hashes.find(longest_run).unwrap();
-    (position, longest_run.len())
-}
-
-async fn count_ways_async(i: usize, input: &str, spec: &[usize]) -> u128 {
-    count_ways(i, input, spec)
-}
 
-fn count_ways(i: usize, input: &str, spec: &[usize]) -> u128 {
-    let rv = count_ways_impl(None, input, spec, false);
-    /*
-    let poss_hash = input
-        .chars()
-        .filter(|&e| e == '#' || e == '?')
-        .collect::<Vec<_>>()
-        .len();
-    let poss_dot = input
-        .chars()
-        .filter(|&e| e == '.' || e == '?')
-        .collect::<Vec<_>>()
-        .len();
-    println!("{} poss_hash: {}, poss_dot: {}", input, poss_hash, poss_dot);
-    let rv = count_ways_impl2(
-        None,
-        input,
-        None,
-        spec,
-        false,
-        poss_hash,
-        poss_dot,
-        longest_run(input),
-    );
-    */
-    println!("{}: {} {:?} = {}", i, input, spec, rv);
-    rv
+pub fn unfold(springs: &str, groups: &[usize], factor: usize) -> (String, Vec<usize>) {
+    let springs = (0..factor).map(|_| springs).join("?");
+    let groups = groups.repeat(factor);
+    (springs, groups)
 }
 
-fn count_ways_impl(first: Option<&str>, input: &str, spec: &[usize], in_run: bool) -> u128 {
-    println!("Checking {:?}/{} {:?}", first, input, spec);
-    if input.is_empty() && first.is_none() {
-        if spec.is_empty() {
-            println!("spec empty - yes");
-            return 1;
+/// Counted over (group index, current run length) states as each spring is read.
+pub fn count_ways(springs: &str, groups: &[usize]) -> u128 {
+    let longest = groups.iter().copied().max().unwrap_or(0);
+    let mut ways = vec![vec![0_u128; longest + 1]; groups.len() + 1];
+    ways[0][0] = 1;
+    for c in springs.chars() {
+        let mut next = vec![vec![0_u128; longest + 1]; groups.len() + 1];
+        for (group, runs) in ways.iter().enumerate() {
+            for (run, &count) in runs.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+                if c != '.' && group < groups.len() && run < groups[group] {
+                    next[group][run + 1] += count;
+                }
+                if c != '#' {
+                    if run == 0 {
+                        next[group][0] += count;
+                    } else if run == groups[group] {
+                        next[group + 1][0] += count;
+                    }
+                }
+            }
         }
-        if spec == vec![0] {
-            println!("spec 0 - yes");
-            return 1;
-        }
-        println!("input empty - no");
-        return 0;
+        ways = next;
     }
-    let (first, input) = match first {
-        Some(v) => (v, input),
-        None => (&input[0..1], &input[1..]),
+    let finished = ways[groups.len()][0];
+    let closing = match groups.last() {
+        Some(&last) => ways[groups.len() - 1][last],
+        None => 0,
     };
-    match first {
-        "#" if spec.is_empty() => 0,
-        "#" if spec[0] == 0 => 0,
-        "#" => {
-            let mut new_spec = spec.to_vec();
-            new_spec[0] -= 1;
-            count_ways_impl(None, input, &new_spec[..], true)
-        }
-        "." if spec.is_empty() => count_ways_impl(None, input, spec, false),
-        "." if spec[0] == 0 => count_ways_impl(None, input, &spec[1..], false),
-        "." if !in_run => count_ways_impl(None, input, spec, false),
-        "." => 0,
-        "?" => {
-            count_ways_impl(Some("#"), input, spec, in_run)
-                + count_ways_impl(Some("."), input, spec, in_run)
-        }
-        _ => panic!("Not possible"),
-    }
+    finished + closing
 }
 
-fn count_ways_impl2(
-    first: Option<&str>,
-    input: &str,
-    first_spec: Option<usize>,
-    spec: &[usize],
-    in_run: bool,
-    poss_hash: usize,
-    poss_dot: usize,
-    run: (usize, usize),
-) -> u128 {
-    println!(
-        "{:?}/{} {:?}/{:?}  poss_hash: {}, poss_dot: {}",
-        first, input, first_spec, spec, poss_hash, poss_dot
-    );
-    if input.is_empty() && first.is_none() {
-        if spec.is_empty() {
-            println!("empty spec - yes");
-            return 1;
-        }
-        if spec == vec![0] {
-            println!("spec 0 - yes");
-            return 1;
-        }
-        println!("input empty - no");
-        return 0;
-    }
-    if spec.is_empty() && first_spec.is_none() {
-        if input.find('#').is_some() {
-            println!("empty spec, hashes - no");
-            return 0;
-        }
-
-        if poss_dot == input.len() + (if first.is_none() { 0 } else { 1 }) {
-            println!("empty spec, all dots - yes");
-            return 1;
-        }
-    }
-
-    let spec_len = spec.len() + (if first_spec.is_none() { 0 } else { 1 });
-    if poss_dot < spec_len - 1 {
-        println!(
-            "pos_dot ({}) < spec_len-1 ({}) - no",
-            poss_dot,
-            spec_len - 1
-        );
-        return 0;
-    }
-
-    if spec.iter().sum::<usize>() + first_spec.unwrap_or(0)
-        > input
-            .chars()
-            .filter(|&e| e == '#')
-            .collect::<Vec<_>>()
-            .len()
-            + (if let Some("#") = first { 1 } else { 0 })
-    {
-        println!("not enough hashes - no");
-        return 0;
-    }
-
-    let first_spec_val = if let Some(v) = first_spec { v } else { 0 };
-    if spec.iter().sum::<usize>() + first_spec_val > poss_hash {
-        return 0;
-    }
-
-    let (first, input) = match first {
-        Some(v) => (v, input),
-        None => (&input[0..1], &input[1..]),
-    };
-
-    let (first_spec, spec) = match first_spec {
-        Some(v) => (v, spec),
-        None => (spec[0], &spec[1..]),
-    };
-
-    match first {
-        "#" if spec.is_empty() => 0,
-        "#" if spec[0] == 0 => 0,
-        "#" => {
-            let first_spec = if spec[0] > 1 { Some(spec[0] - 1) } else { None };
-            let spec = &spec[1..];
-            count_ways_impl2(
-                None,
-                input,
-                first_spec,
-                spec,
-                true,
-                poss_hash - 1,
-                poss_dot,
-                run,
-            )
-        }
-        "." if spec.is_empty() => count_ways_impl2(
-            None,
-            input,
-            Some(first_spec),
-            spec,
-            false,
-            poss_hash,
-            poss_dot - 1,
-            run,
-        ),
-        "." if spec[0] == 0 => count_ways_impl2(
-            None,
-            input,
-            None,
-            &spec[1..],
-            false,
-            poss_hash,
-            poss_dot - 1,
-            run,
-        ),
-        "." if !in_run => count_ways_impl2(
-            None,
-            input,
-            Some(first_spec),
-            spec,
-            false,
-            poss_hash,
-            poss_dot - 1,
-            run,
-        ),
-        "." => 0,
-        "?" => {
-            count_ways_impl2(
-                Some("#"),
-                input,
-                Some(first_spec),
-                spec,
-                in_run,
-                poss_hash,
-                poss_dot - 1,
-                run,
-            ) + count_ways_impl2(
-                Some("."),
-                input,
-                Some(first_spec),
-                spec,
-                in_run,
-                poss_hash - 1,
-                poss_dot,
-                run,
-            )
-        }
-        _ => panic!("Not possible"),
-    }
-}
-async fn part1(data: &str) -> u128 {
-    let lines = parse::springs(data).unwrap().1;
-    let mut tasks = lines
-        .into_iter()
-        .enumerate()
-        .map(|(i, (springs, spec, _result))| {
-            spawn(async move { count_ways_async(i, &springs, &spec).await })
-        })
-        .collect::<Vec<_>>();
-    let mut total = 0;
-    loop {
-        let completed = select_all(tasks).await;
-        let result = completed.0.expect("task failed");
-        total += result;
-        tasks = completed.2;
-        if tasks.is_empty() {
-            break;
-        }
-    }
-    total
-    /*
-    lines
-        .iter()
-        .enumerate()
-        .map(|(i, (springs, spec))| {
-            //println!("Checking {}", i);
-            count_ways(&springs[..], &spec[..])
+fn total(records: &[Record], factor: usize) -> u128 {
+    records
+        .par_iter()
+        .map(|(springs, groups, _)| {
+            let (springs, groups) = unfold(springs, groups, factor);
+            count_ways(&springs, &groups)
         })
         .sum()
-        */
 }
 
-async fn part2(data: &str) -> u128 {
-    let lines = parse::springs(data).unwrap().1;
-    let mut tasks = lines
-        .into_iter()
-        .map(|(springs, spec, _result)| duplicate(springs, spec, 5, None))
-        .enumerate()
-        .map(|(i, (springs, spec, _result))| {
-            spawn(async move { count_ways_async(i, &springs, &spec).await })
-        })
-        .collect::<Vec<_>>();
-    let mut total = 0;
-    loop {
-        let completed = select_all(tasks).await;
-        let result = completed.0.expect("task failed");
-        total += result;
-        tasks = completed.2;
-        println!("{} tasks left", tasks.len());
-        if tasks.is_empty() {
-            break;
-        }
-    }
-    total
-    /*
-    lines
-        .into_iter()
-        .map(|(springs, spec)| duplicate(springs, spec, 5))
-        .enumerate()
-        .map(|(i, (springs, spec))| {
-            //println!("Checking {}", i);
-            count_ways(i, &springs[..], &spec[..])
-        })
-        .sum()
-        */
+fn part1(data: &str) -> u128 {
+    let records = parse::springs(data).unwrap().1;
+    total(&records, 1)
+}
+
+fn part2(data: &str) -> u128 {
+    let records = parse::springs(data).unwrap().1;
+    total(&records, 5)
 }
 
-#[tokio::main()]
-async fn main() {
+fn main() {
     println!("Day x of {}", version());
-    let val = part1(input()).await;
+    let val = part1(input());
     println!("Part 1 answer is {}", val);
-    //let val = part2(input()).await;
-    //println!("Part 2 answer is {}", val);
+    let val = part2(input());
+    println!("Part 2 answer is {}", val);
 }
 
 mod parse {
@@ -349,7 +90,7 @@ mod parse {
     use nom::sequence::terminated;
     use nom::{bytes::complete::tag, IResult};
 
-    pub fn inputline(i: &str) -> IResult<&str, (String, Vec<usize>, Option<u128>)> {
+    pub fn inputline(i: &str) -> IResult<&str, Record> {
         let (i, states) = terminated(many1(one_of("#.?")), tag(" "))(i)?;
         let (i, vals) = separated_list1(tag(","), map(digit1, |s: &str| s.parse().unwrap()))(i)?;
         let (i, result) = opt(preceded(
@@ -359,7 +100,7 @@ mod parse {
         Ok((i, (states.iter().collect::<String>(), vals, result)))
     }
 
-    pub fn springs(i: &str) -> IResult<&str, Vec<(String, Vec<usize>, Option<u128>)>> {
+    pub fn springs(i: &str) -> IResult<&str, Vec<Record>> {
         separated_list1(line_ending, inputline)(i)
     }
 }
@@ -372,6 +113,39 @@ mod tests {
         include_str!("sample.txt")
     }
 
+    fn partial() -> &'static str {
+        include_str!("partial.txt")
+    }
+
+    fn brute_force(springs: &str, groups: &[usize]) -> u128 {
+        let unknown = springs.chars().filter(|&e| e == '?').count();
+        (0..1_u32 << unknown)
+            .filter(|mask| {
+                let mut bit = 0;
+                let filled = springs
+                    .chars()
+                    .map(|e| {
+                        if e != '?' {
+                            return e;
+                        }
+                        bit += 1;
+                        if mask & (1 << (bit - 1)) != 0 {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>();
+                let runs = filled
+                    .split('.')
+                    .filter(|e| !e.is_empty())
+                    .map(|e| e.len())
+                    .collect::<Vec<_>>();
+                runs == groups
+            })
+            .count() as u128
+    }
+
     #[test]
     fn test_parse() {
         let lines = parse::springs(sample()).unwrap().1;
@@ -392,40 +166,64 @@ mod tests {
         assert_eq!(Some(162), lines[2].2);
     }
 
+    #[test]
+    fn test_partial_results() {
+        for (springs, groups, result) in parse::springs(partial()).unwrap().1 {
+            assert_eq!(result, Some(count_ways(&springs, &groups)), "{}", springs);
+        }
+    }
+
     #[test]
     fn test_part_1() {
         let lines = parse::springs(sample()).unwrap().1;
         let results = lines
-            .into_iter()
-            .enumerate()
-            .map(|(i, (springs, spec, _result))| count_ways(i, &springs, &spec))
+            .iter()
+            .map(|(springs, spec, _result)| count_ways(springs, spec))
             .collect::<Vec<_>>();
 
-        assert_eq!(21, results.iter().sum::<u128>());
+        assert_eq!(vec![1, 4, 1, 1, 4, 10], results);
+        assert_eq!(21, part1(sample()));
     }
 
     #[test]
     fn test_sample_part_2() {
-        let lines = parse::springs(sample()).unwrap().1;
-        let results = lines
-            .into_iter()
-            .map(|(springs, spec, _result)| duplicate(springs, spec, 5, None))
-            .enumerate()
-            .map(|(i, (springs, spec, _result))| count_ways(i, &springs, &spec))
-            .collect::<Vec<_>>();
-
-        assert_eq!(525152, results.iter().sum::<u128>());
+        assert_eq!(525152, part2(sample()));
     }
 
-    /*
     #[test]
-    fn test_long() {
-        let (input, spec) = duplicate("??..?.??.?.??.".to_owned(), vec![1, 1, 1], 5);
-        assert_eq!(0, count_ways(&input, &spec));
+    fn test_unfold() {
+        assert_eq!((".#?.#".to_owned(), vec![1, 1]), unfold(".#", &[1], 2));
     }
-    */
+
     #[test]
     fn test_broken() {
-        assert_eq!(1, count_ways(0, "?.#.??.#.#", &[1, 1, 1]));
+        assert_eq!(1, count_ways("?.#.??.#.#", &[1, 1, 1]));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut seed = 0x2545_f491_u32;
+        let mut random = move |limit: u32| {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed % limit
+        };
+        for _ in 0..500 {
+            let len = 1 + random(12) as usize;
+            let springs = (0..len)
+                .map(|_| ['.', '#', '?'][random(3) as usize])
+                .collect::<String>();
+            let groups = (0..random(4))
+                .map(|_| 1 + random(4) as usize)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                brute_force(&springs, &groups),
+                count_ways(&springs, &groups),
+                "{} {:?}",
+                springs,
+                groups
+            );
+        }
     }
 }
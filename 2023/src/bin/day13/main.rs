@@ -1,168 +1,20 @@
 use aoc_2023::aoc::*;
-use std::iter::zip;
+use mirror::*;
 
-#[derive(Debug, Clone)]
-pub struct Grid(Vec<String>);
-
-impl Grid {
-    pub fn horizontal_reflection(&self) -> Option<u32> {
-        Self::find_reflection(&self.0)
-    }
-
-    pub fn unsmudged_horizontal_reflection(&self) -> Option<u32> {
-        if let Some(unsmudged) = Self::unsmudge(&self.0) {
-            for g in unsmudged {
-                if let Some(r) = Self::find_reflection(&g) {
-                    return Some(r);
-                }
-            }
-            None
-        } else {
-            println!("Can't find horizontal smudge");
-            None
-        }
-    }
-
-    pub fn unsmudged_vertical_reflection(&self) -> Option<u32> {
-        let lines = Self::transpose(&self.0);
-        if let Some(unsmudged) = Self::unsmudge(&lines) {
-            println!("Unsmudged vertical");
-            for g in unsmudged {
-                if let Some(r) = Self::find_reflection(&g) {
-                    return Some(r);
-                }
-            }
-            None
-        } else {
-            None
-        }
-    }
-
-    pub fn line_as_int(line: &String) -> u64 {
-        line.chars()
-            .fold(0, |a, e| (a << 1) + if e == '.' { 0 } else { 1 })
-    }
-
-    pub fn has_single_difference(l1: &String, l2: &String) -> bool {
-        let v1 = Self::line_as_int(l1);
-        let v2 = Self::line_as_int(l2);
-        let diff = v1 ^ v2;
-        /*
-        println!(
-            "{} l1\n{} l2\n v1 {}, v2 {}, diff {}",
-            l1,
-            l2,
-            v1,
-            v2,
-            diff.count_ones()
-        );*/
-        diff.count_ones() == 1
-    }
-
-    pub fn unsmudge(lines: &Vec<String>) -> Option<(Vec<Vec<String>>)> {
-        let mut ret = vec![];
-        for i in 0..lines.len() - 1 {
-            for j in i..lines.len() {
-                if Self::has_single_difference(&lines[i], &lines[j]) {
-                    let mut left = lines.clone();
-                    let mut right = lines.clone();
-                    left[i] = lines[j].clone();
-                    right[j] = lines[i].clone();
-                    ret.push(left);
-                    ret.push(right);
-                    println!("Replacing {} with {}", i, j);
-                }
-            }
-        }
-        if ret.is_empty() {
-            None
-        } else {
-            Some(ret)
-        }
-    }
-
-    fn find_reflection(lines: &Vec<String>) -> Option<u32> {
-        let end = lines.len();
-        for i in 1..end {
-            println!(
-                "Comparing {} and {}\n{}\n{}",
-                i - 1,
-                i,
-                lines[i - 1],
-                lines[i]
-            );
-            if lines[i - 1] == lines[i] {
-                println!("Considering {}", i);
-                let mut matches = true;
-                for (i1, i2) in zip((0..i).rev(), i..end) {
-                    println!("{} vs {}", i1, i2);
-                    matches = matches && (lines[i1] == lines[i2])
-                }
-                if matches {
-                    return Some(i as u32);
-                }
-            }
-        }
-        None
-    }
-    fn transpose(input: &Vec<String>) -> Vec<String> {
-        let intermediate = input
-            .iter()
-            .map(|e| e.chars().collect::<Vec<_>>())
-            .collect::<Vec<_>>();
-        (0..intermediate[0].len())
-            .map(|i| {
-                intermediate
-                    .iter()
-                    .map(|inner| inner[i])
-                    .collect::<Vec<_>>()
-            })
-            .map(|e| e.iter().collect::<String>())
-            .collect()
-    }
-
-    pub fn vertical_reflection(&self) -> Option<u32> {
-        let lines = Self::transpose(&self.0);
-        Self::find_reflection(&lines)
-    }
-    pub fn score(&self, i: usize) -> u32 {
-        if let Some(v) = self.horizontal_reflection() {
-            return 100 * v;
-        }
-        if let Some(v) = self.vertical_reflection() {
-            return v;
-        }
-        panic!("Not found in {} {:?}", i, &self);
-    }
-
-    pub fn unsmudged_score(&self, i: usize) -> u32 {
-        println!("Processing {}", i);
-        if let Some(v) = self.unsmudged_horizontal_reflection() {
-            return 100 * v;
-        }
-        if let Some(v) = self.unsmudged_vertical_reflection() {
-            return v;
-        }
-        panic!("Not found in {} {:?}", i, &self);
-    }
-}
+pub mod mirror;
 
 fn input() -> &'static str {
     include_str!("input.txt")
 }
 
-fn part1(data: &str) -> u32 {
-    let grids = parse::grids(data).unwrap().1;
-    grids.iter().enumerate().map(|(i, e)| e.score(i)).sum()
+fn part1(data: &str) -> usize {
+    let patterns = parse::patterns(data).unwrap().1;
+    patterns.iter().map(|e| e.summarize(0)).sum()
 }
 
-fn part2(data: &str) -> u32 {
-    let grids = parse::grids(data).unwrap().1;
-    grids
-        .iter()
-        .enumerate()
-        .map(|(i, e)| e.unsmudged_score(i))
-        .sum()
+fn part2(data: &str) -> usize {
+    let patterns = parse::patterns(data).unwrap().1;
+    patterns.iter().map(|e| e.summarize(1)).sum()
 }
 
 fn main() {
@@ -175,30 +27,19 @@ fn main() {
 
 mod parse {
     use super::*;
-    use nom::character::complete::digit1;
     use nom::character::complete::line_ending;
     use nom::character::complete::one_of;
-    use nom::combinator::map;
+    use nom::combinator::recognize;
     use nom::multi::many1;
     use nom::multi::separated_list1;
-    use nom::sequence::delimited;
-    use nom::sequence::pair;
-    use nom::sequence::preceded;
-    use nom::sequence::terminated;
-    use nom::{bytes::complete::tag, IResult};
+    use nom::IResult;
 
-    pub fn grid(i: &str) -> IResult<&str, Grid> {
-        let (i, lines) = separated_list1(
-            line_ending,
-            map(many1(one_of("#.")), |e| e.iter().collect::<String>()),
-        )(i)?;
-        Ok((
-            i,
-            Grid(lines.iter().map(|e| e.to_owned()).collect::<Vec<_>>()),
-        ))
+    pub fn pattern(i: &str) -> IResult<&str, Pattern> {
+        let (i, lines) = separated_list1(line_ending, recognize(many1(one_of("#."))))(i)?;
+        Ok((i, Pattern::new(&lines)))
     }
-    pub fn grids(i: &str) -> IResult<&str, Vec<Grid>> {
-        separated_list1(many1(line_ending), grid)(i)
+    pub fn patterns(i: &str) -> IResult<&str, Vec<Pattern>> {
+        separated_list1(many1(line_ending), pattern)(i)
     }
 }
 
@@ -210,14 +51,19 @@ mod tests {
         include_str!("sample.txt")
     }
 
+    fn lines(reflections: Vec<Reflection>) -> Vec<(Axis, usize)> {
+        reflections.iter().map(|e| (e.axis, e.line)).collect()
+    }
+
     #[test]
     fn test_sample() {
-        let grids = parse::grids(sample()).unwrap().1;
-        println!("{:?}", grids);
-        assert_eq!(None, grids[0].horizontal_reflection());
-        assert_eq!(Some(4), grids[1].horizontal_reflection());
-        assert_eq!(Some(5), grids[0].vertical_reflection());
-        assert_eq!(None, grids[1].vertical_reflection());
+        let patterns = parse::patterns(sample()).unwrap().1;
+        assert_eq!(2, patterns.len());
+        assert_eq!(vec![(Axis::Vertical, 5)], lines(patterns[0].reflections(0)));
+        assert_eq!(
+            vec![(Axis::Horizontal, 4)],
+            lines(patterns[1].reflections(0))
+        );
     }
     #[test]
     fn test_sample_part_1() {
@@ -226,49 +72,35 @@ mod tests {
 
     #[test]
     fn test_smudges() {
-        assert!(Grid::has_single_difference(
-            &"....#".to_owned(),
-            &".#..#".to_owned()
-        ));
-        assert!(!Grid::has_single_difference(
-            &"....#".to_owned(),
-            &"##..#".to_owned()
-        ));
-        assert!(!Grid::has_single_difference(
-            &"#.##..##.".to_owned(),
-            &"..#.##.#.".to_owned()
-        ));
-
-        let unsmudged = Grid::unsmudge(
-            &"#...##..#
-#....#..#
-..##..###
-#####.##.
-#####.##.
-..##..###
-#....#..#"
-                .lines()
-                .map(|e| e.to_owned())
-                .collect(),
+        let patterns = parse::patterns(sample()).unwrap().1;
+        assert_eq!(
+            vec![(Axis::Horizontal, 3)],
+            lines(patterns[0].reflections(1))
         );
-        let unsmudged = unsmudged.unwrap();
-        assert_eq!(unsmudged[0][0], unsmudged[0][1]);
+        assert_eq!(
+            vec![(Axis::Horizontal, 1)],
+            lines(patterns[1].reflections(1))
+        );
+        assert_eq!(300, patterns[0].summarize(1));
+    }
 
-        let unsmudged = Grid::unsmudge(
-            &"#.##..##.
-..#.##.#.
-##......#
-##......#
-..#.##.#.
-..##..##.
-#.#.##.#."
-                .lines()
-                .map(|e| e.to_owned())
-                .collect(),
+    #[test]
+    fn test_candidates() {
+        let pattern = Pattern::new(&["#..#", "#..#"]);
+        assert_eq!(
+            vec![(Axis::Horizontal, 1), (Axis::Vertical, 2)],
+            lines(pattern.reflections(0))
+        );
+        assert_eq!(102, pattern.summarize(0));
+        let pattern = Pattern::new(&["#...", "...#"]);
+        assert_eq!(
+            vec![(Axis::Vertical, 1), (Axis::Vertical, 3)],
+            lines(pattern.reflections(1))
+        );
+        assert_eq!(
+            vec![(Axis::Horizontal, 1), (Axis::Vertical, 2)],
+            lines(pattern.reflections(2))
         );
-        let unsmudged = unsmudged.unwrap();
-        println!("{:?}", unsmudged[0]);
-        assert_eq!(unsmudged[0][0], unsmudged[0][5]);
     }
 
     #[test]
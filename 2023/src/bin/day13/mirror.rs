@@ -0,0 +1,79 @@
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pattern {
+    pub rows: Vec<u64>,
+    pub columns: Vec<u64>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    /// A mirror lying between two rows.
+    Horizontal,
+    /// A mirror lying between two columns.
+    Vertical,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reflection {
+    pub axis: Axis,
+    pub line: usize,
+    pub smudges: u32,
+}
+
+impl Reflection {
+    pub fn score(&self) -> usize {
+        match self.axis {
+            Axis::Horizontal => 100 * self.line,
+            Axis::Vertical => self.line,
+        }
+    }
+}
+
+impl Pattern {
+    pub fn new(lines: &[&str]) -> Self {
+        let width = lines.first().map_or(0, |e| e.len());
+        assert!(width <= 64 && lines.len() <= 64, "Pattern too big");
+        let rock = |y: usize, x: usize| lines[y].as_bytes()[x] == b'#';
+        let rows = (0..lines.len())
+            .map(|y| (0..width).fold(0, |a, x| a << 1 | rock(y, x) as u64))
+            .collect();
+        let columns = (0..width)
+            .map(|x| (0..lines.len()).fold(0, |a, y| a << 1 | rock(y, x) as u64))
+            .collect();
+        Self { rows, columns }
+    }
+
+    fn lines(masks: &[u64], smudges: u32) -> Vec<usize> {
+        (1..masks.len())
+            .filter(|&line| {
+                let mut differences = 0;
+                for (a, b) in masks[..line].iter().rev().zip(&masks[line..]) {
+                    differences += (a ^ b).count_ones();
+                    if differences > smudges {
+                        return false;
+                    }
+                }
+                differences == smudges
+            })
+            .collect()
+    }
+
+    pub fn reflections(&self, smudges: u32) -> Vec<Reflection> {
+        let found = |axis, masks| {
+            Self::lines(masks, smudges)
+                .into_iter()
+                .map(move |line| Reflection {
+                    axis,
+                    line,
+                    smudges,
+                })
+        };
+        found(Axis::Horizontal, &self.rows)
+            .chain(found(Axis::Vertical, &self.columns))
+            .collect()
+    }
+
+    /// The puzzle promises exactly one reflection per pattern.
+    pub fn summarize(&self, smudges: u32) -> usize {
+        self.reflections(smudges).iter().map(|e| e.score()).sum()
+    }
+}
@@ -1,79 +1,21 @@
 use aoc_2023::aoc::*;
+use platform::*;
 
-#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
-pub enum Rock {
-    Round,
-    Square,
-    Empty,
-}
-
-fn cycle(mut plate: Vec<Vec<Rock>>) -> Vec<Vec<Rock>> {
-    let mut plate = plate;
-    let mut tilted;
-    for _ in 0..4 {
-        tilted = plate.into_iter().map(tilt).collect::<Vec<Vec<Rock>>>();
-        plate = transpose(&tilted);
-    }
-    println!("{:?}", &plate);
-    plate
-}
-
-fn tilt(mut row: Vec<Rock>) -> Vec<Rock> {
-    let mut current = 0;
-    let end = row.len();
-    let mut empty;
-    loop {
-        while current < end && row[current] != Rock::Empty {
-            current += 1
-        }
-        println!("empty is {}", current);
-        empty = current;
-        while current < end && row[current] == Rock::Empty {
-            current += 1
-        }
-        if current == end {
-            return row;
-        }
-        if row[current] == Rock::Round {
-            println!("round is {}, swapping", current);
-            row[empty] = Rock::Round;
-            row[current] = Rock::Empty;
-            current = empty + 1
-        }
-        if row[current] == Rock::Square {
-            println!("square is {}, moving on", current);
-            current += 1;
-        }
-    }
-}
-
-fn load(row: &Vec<Rock>) -> u64 {
-    row.iter().rev().enumerate().fold(0, |a, (i, &e)| {
-        if e == Rock::Round {
-            a + (i + 1) as u64
-        } else {
-            a
-        }
-    })
-}
+pub mod platform;
 
 fn input() -> &'static str {
     include_str!("input.txt")
 }
 
 fn part1(data: &str) -> u64 {
-    let plate = parse::plate(data).unwrap().1;
-    let tilted = plate.into_iter().map(tilt).collect::<Vec<Vec<_>>>();
-    tilted.iter().map(load).sum::<u64>()
+    let mut platform = parse::platform(data).unwrap().1;
+    platform.tilt(Direction::North);
+    platform.load()
 }
 
-fn part2(data: &str, count: u64) -> u64 {
-    let mut plate = parse::plate(data).unwrap().1;
-    for _ in 0..count {
-        plate = cycle(plate);
-    }
-    println!("{:?}", plate);
-    plate.iter().map(load).sum::<u64>()
+fn part2(data: &str, count: usize) -> u64 {
+    let platform = parse::platform(data).unwrap().1;
+    platform.spin(count).load()
 }
 
 fn main() {
@@ -86,30 +28,20 @@ fn main() {
 
 mod parse {
     use super::*;
-    use nom::character::complete::digit1;
     use nom::character::complete::line_ending;
     use nom::character::complete::one_of;
-    use nom::combinator::map;
+    use nom::combinator::recognize;
     use nom::multi::many1;
     use nom::multi::separated_list1;
-    use nom::sequence::delimited;
-    use nom::sequence::pair;
-    use nom::sequence::preceded;
-    use nom::sequence::terminated;
-    use nom::{bytes::complete::tag, IResult};
-
-    pub fn row(i: &str) -> IResult<&str, Vec<Rock>> {
-        many1(map(one_of("O.#"), |e| match e {
-            '.' => Rock::Empty,
-            'O' => Rock::Round,
-            '#' => Rock::Square,
-            _ => panic!("Bad match"),
-        }))(i)
+    use nom::IResult;
+
+    pub fn row(i: &str) -> IResult<&str, &str> {
+        recognize(many1(one_of("O.#")))(i)
     }
 
-    pub fn plate(i: &str) -> IResult<&str, Vec<Vec<Rock>>> {
+    pub fn platform(i: &str) -> IResult<&str, Platform> {
         let (i, lines) = separated_list1(line_ending, row)(i)?;
-        Ok((i, transpose(&lines)))
+        Ok((i, Platform::new(&lines)))
     }
 }
 
@@ -123,36 +55,67 @@ mod tests {
 
     #[test]
     fn test_parse() {
-        let plate = parse::plate(sample()).unwrap().1;
-        assert_eq!(Rock::Round, plate[0][0]);
-        assert_eq!(Rock::Round, plate[0][1]);
-        assert_eq!(Rock::Empty, plate[0][2]);
+        let platform = parse::platform(sample()).unwrap().1;
+        assert_eq!((10, 10), (platform.width, platform.height()));
+        assert_eq!(0b10_0001, platform.round[0] | platform.square[0]);
+        assert_eq!(sample().trim_end(), platform.to_string().trim_end());
     }
 
     #[test]
     fn test_tilt() {
-        let row = vec![
-            Rock::Empty,
-            Rock::Empty,
-            Rock::Round,
-            Rock::Square,
-            Rock::Round,
-            Rock::Empty,
-            Rock::Round,
-        ];
-        let row = tilt(row);
-        assert_eq!(
-            vec![
-                Rock::Round,
-                Rock::Empty,
-                Rock::Empty,
-                Rock::Square,
-                Rock::Round,
-                Rock::Round,
-                Rock::Empty
-            ],
-            row
-        );
+        let mut platform = Platform::new(&["..O#O.O"]);
+        platform.tilt(Direction::West);
+        assert_eq!("O..#OO.\n", platform.to_string());
+        platform.tilt(Direction::East);
+        assert_eq!("..O#.OO\n", platform.to_string());
+        let mut platform = Platform::new(&[".", "O", "#", ".", "O"]);
+        platform.tilt(Direction::North);
+        assert_eq!("O\n.\n#\nO\n.\n", platform.to_string());
+        platform.tilt(Direction::South);
+        assert_eq!(".\nO\n#\n.\nO\n", platform.to_string());
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut platform = Platform::new(&[]);
+        platform.cycle();
+        assert_eq!((0, 0), (platform.width, platform.height()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Platform rows differ in width")]
+    fn test_ragged() {
+        Platform::new(&["O.", "O"]);
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut platform = parse::platform(sample()).unwrap().1;
+        platform.cycle();
+        let expected = "\
+.....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+";
+        assert_eq!(expected, platform.to_string());
+        assert_eq!(platform, parse::platform(sample()).unwrap().1.spin(1));
+    }
+
+    #[test]
+    fn test_spin() {
+        let platform = parse::platform(sample()).unwrap().1;
+        for count in 0..40 {
+            let mut slow = platform.clone();
+            (0..count).for_each(|_| slow.cycle());
+            assert_eq!(slow, platform.spin(count), "after {} cycles", count);
+        }
     }
 
     #[test]
@@ -162,6 +125,6 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        assert_eq!(64, part2(sample(), 1));
+        assert_eq!(64, part2(sample(), 1000000000));
     }
 }
@@ -0,0 +1,155 @@
+use aoc_2023::aoc::Direction;
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// One bitmask per row, with bit `x` for column `x`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Platform {
+    pub width: usize,
+    pub round: Vec<u128>,
+    pub square: Vec<u128>,
+}
+
+impl Platform {
+    pub fn new(rows: &[&str]) -> Self {
+        let width = rows.first().map_or(0, |e| e.len());
+        assert!(width <= 128, "Platform too wide: {}", width);
+        assert!(
+            rows.iter().all(|e| e.len() == width),
+            "Platform rows differ in width"
+        );
+        let mask = |row: &str, c: char| {
+            row.chars()
+                .enumerate()
+                .filter(|&(_, e)| e == c)
+                .fold(0u128, |a, (x, _)| a | 1 << x)
+        };
+        Self {
+            width,
+            round: rows.iter().map(|e| mask(e, 'O')).collect(),
+            square: rows.iter().map(|e| mask(e, '#')).collect(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.round.len()
+    }
+
+    fn full(&self) -> u128 {
+        if self.width == 128 {
+            u128::MAX
+        } else {
+            (1 << self.width) - 1
+        }
+    }
+
+    pub fn tilt(&mut self, direction: Direction) {
+        if self.height() == 0 {
+            return;
+        }
+        match direction {
+            Direction::North => self.tilt_rows((1..self.height()).map(|y| (y, y - 1))),
+            Direction::South => self.tilt_rows((0..self.height() - 1).rev().map(|y| (y, y + 1))),
+            Direction::West => {
+                let full = self.full();
+                for y in 0..self.height() {
+                    loop {
+                        let free = !(self.round[y] | self.square[y]) & full;
+                        let moving = self.round[y] & (free << 1);
+                        if moving == 0 {
+                            break;
+                        }
+                        self.round[y] = (self.round[y] & !moving) | moving >> 1;
+                    }
+                }
+            }
+            Direction::East => {
+                let full = self.full();
+                for y in 0..self.height() {
+                    loop {
+                        let free = !(self.round[y] | self.square[y]) & full;
+                        let moving = self.round[y] & (free >> 1);
+                        if moving == 0 {
+                            break;
+                        }
+                        self.round[y] = (self.round[y] & !moving) | moving << 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn tilt_rows(&mut self, pairs: impl Iterator<Item = (usize, usize)> + Clone) {
+        loop {
+            let mut moved = false;
+            for (from, to) in pairs.clone() {
+                let moving = self.round[from] & !(self.round[to] | self.square[to]);
+                if moving != 0 {
+                    self.round[from] &= !moving;
+                    self.round[to] |= moving;
+                    moved = true;
+                }
+            }
+            if !moved {
+                return;
+            }
+        }
+    }
+
+    pub fn cycle(&mut self) {
+        for direction in [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ] {
+            self.tilt(direction);
+        }
+    }
+
+    /// Jumps ahead once a state repeats.
+    pub fn spin(&self, count: usize) -> Self {
+        let mut seen: HashMap<Vec<u128>, usize> = HashMap::new();
+        let mut history = vec![];
+        let mut platform = self.clone();
+        for i in 0..count {
+            if let Some(&start) = seen.get(&platform.round) {
+                let period = i - start;
+                let round = history.swap_remove(start + (count - start) % period);
+                return Self { round, ..platform };
+            }
+            seen.insert(platform.round.clone(), i);
+            history.push(platform.round.clone());
+            platform.cycle();
+        }
+        platform
+    }
+
+    pub fn load(&self) -> u64 {
+        let height = self.height();
+        self.round
+            .iter()
+            .enumerate()
+            .map(|(y, e)| e.count_ones() as u64 * (height - y) as u64)
+            .sum()
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (round, square) in self.round.iter().zip(self.square.iter()) {
+            for x in 0..self.width {
+                let c = if round & 1 << x != 0 {
+                    'O'
+                } else if square & 1 << x != 0 {
+                    '#'
+                } else {
+                    '.'
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,6 @@
 use aoc_2023::aoc::*;
-
-#[derive(Debug, Default, Eq, PartialEq)]
-pub struct Map {
-    boxes: Vec<Vec<Entry>>,
-}
+use aoc_2023::boxmap::*;
+use std::fmt::Display;
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum Operation {
@@ -12,84 +9,49 @@ pub enum Operation {
 }
 
 impl Operation {
-    pub fn label(&self) -> String {
+    pub fn label(&self) -> &str {
         match self {
-            Self::Insert(lbl, _) => lbl.clone(),
-            Self::Remove(lbl) => lbl.clone(),
+            Self::Insert(lbl, _) => lbl,
+            Self::Remove(lbl) => lbl,
         }
     }
-}
 
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub enum Entry {
-    Occupied(String, u8),
-    Empty,
-}
-
-impl Entry {
-    pub fn is_occupied(&self, label: &str) -> bool {
+    pub fn apply(&self, map: &mut BoxMap<String, u8>) {
         match self {
-            Self::Occupied(l, _) => l == label,
-            _ => false,
+            Self::Insert(label, val) => {
+                map.insert(label.clone(), *val);
+            }
+            Self::Remove(label) => {
+                map.remove(label);
+            }
         }
     }
 }
 
-impl Map {
-    pub fn new() -> Self {
-        Self {
-            boxes: vec![vec![]; 256],
+impl Display for Operation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Insert(lbl, val) => write!(f, "{}={}", lbl, val),
+            Self::Remove(lbl) => write!(f, "{}-", lbl),
         }
     }
+}
 
-    pub fn power(&self) -> u64 {
-        self.boxes
-            .iter()
-            .enumerate()
-            .map(|(boxid, bucket)| {
-                bucket
-                    .iter()
-                    .enumerate()
-                    .map(|(slotid, entry)| {
-                        (boxid as u64 + 1)
-                            * (slotid as u64 + 1)
-                            * (if let Entry::Occupied(_, v) = entry {
-                                *v as u64
-                            } else {
-                                0
-                            })
-                    })
-                    .sum::<u64>()
-            })
-            .sum::<u64>()
-    }
-
-    pub fn compact(&mut self) {
-        for id in 0..self.boxes.len() {
-            self.boxes[id] = self.boxes[id]
-                .iter()
-                .cloned()
-                .filter(|e| e != &Entry::Empty)
-                .collect::<Vec<Entry>>();
+pub fn arrange(ops: &[Operation], trace: bool) -> BoxMap<String, u8> {
+    let mut map = BoxMap::new();
+    for op in ops {
+        op.apply(&mut map);
+        if trace {
+            println!("After \"{}\":\n{}", op, map);
         }
     }
+    map
+}
 
-    pub fn apply(&mut self, op: &Operation) {
-        let id = hash(&op.label());
-        match op {
-            Operation::Insert(label, val) => {
-                match self.boxes[id].iter().position(|e| e.is_occupied(label)) {
-                    Some(i) => self.boxes[id][i] = Entry::Occupied(label.clone(), *val),
-                    None => self.boxes[id].push(Entry::Occupied(label.clone(), *val)),
-                }
-            }
-            Operation::Remove(label) => {
-                if let Some(i) = self.boxes[id].iter().position(|e| e.is_occupied(label)) {
-                    self.boxes[id][i] = Entry::Empty
-                }
-            }
-        }
-    }
+pub fn power(map: &BoxMap<String, u8>) -> u64 {
+    map.iter()
+        .map(|(boxid, slot, _, val)| (boxid as u64 + 1) * (slot as u64 + 1) * *val as u64)
+        .sum()
 }
 
 fn input() -> &'static str {
@@ -98,29 +60,21 @@ fn input() -> &'static str {
 
 fn part1(data: &str) -> u64 {
     let commands = parse::commands(data).unwrap().1;
-    commands.iter().map(|e| hash(e) as u64).sum()
+    commands.iter().map(|e| holiday_hash(e) as u64).sum()
 }
 
 fn part2(data: &str) -> u64 {
     let ops = parse::operations(data).unwrap().1;
-    let mut map = Map::new();
-    ops.iter().map(|e| map.apply(e)).last();
-    map.compact();
-    map.power()
-}
-
-fn hash(input: &String) -> usize {
-    let rv = input.chars().map(|e| e as u32).fold(0, |mut a, e| {
-        a += e;
-        a *= 17;
-        a % 256
-    }) as usize;
-    println!("hash of {} is {}", input, rv);
-    rv
+    power(&arrange(&ops, false))
 }
 
 fn main() {
     println!("Day 15 of {}", version());
+    if std::env::args().any(|e| e == "--trace") {
+        let ops = parse::operations(input()).unwrap().1;
+        arrange(&ops, true);
+        return;
+    }
     let val = part1(input());
     println!("Part 1 answer is {}", val);
     let val = part2(input());
@@ -142,7 +96,7 @@ mod parse {
     pub fn operation(i: &str) -> IResult<&str, Operation> {
         let (i, label) = alpha1(i)?;
         let (i, op) = alt((
-            map(tag("-"), |e| Operation::Remove(label.to_owned())),
+            map(tag("-"), |_| Operation::Remove(label.to_owned())),
             preceded(
                 tag("="),
                 map(value, |e: String| {
@@ -199,7 +153,7 @@ mod tests {
     }
     #[test]
     fn test_hash() {
-        assert_eq!(52, hash(&"HASH".to_owned()));
+        assert_eq!(52, holiday_hash("HASH"));
     }
 
     #[test]
@@ -216,4 +170,19 @@ mod tests {
             part1("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7")
         );
     }
+
+    #[test]
+    fn test_arrange() {
+        let ops = parse::operations("rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7")
+            .unwrap()
+            .1;
+        assert_eq!("cm-", ops[1].to_string());
+        assert_eq!("ot=7", ops[10].to_string());
+        let map = arrange(&ops, false);
+        assert_eq!(
+            "Box 0: [rn 1] [cm 2]\nBox 3: [ot 7] [ab 5] [pc 6]\n",
+            map.to_string()
+        );
+        assert_eq!(1, map.tombstones());
+    }
 }
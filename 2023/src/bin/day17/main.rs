@@ -1,7 +1,9 @@
 use aoc_2023::aoc::*;
 use eframe::egui;
 use egui::{Color32, Painter, Pos2, Rect, Rounding, Sense, Stroke};
-use std::{collections::HashMap, ops::Add, time::Duration};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::{ops::Add, time::Duration};
 
 fn input() -> &'static str {
     include_str!("input.txt")
@@ -13,7 +15,7 @@ pub struct Grid {
     elements: HashMap<Pos, Cell>,
 }
 
-#[derive(Default, Debug, Copy, Clone, Hash, Eq, PartialEq)]
+#[derive(Default, Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub struct Pos {
     x: i32,
     y: i32,
@@ -42,24 +44,23 @@ impl Pos {
     }
 }
 
-#[derive(Default, Debug)]
-pub struct Cell {
-    heatloss: i32,
-    turned_to_exit_north: bool,
-    turned_to_exit_south: bool,
-    turned_to_exit_east: bool,
-    turned_to_exit_west: bool,
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Pos {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+        }
+    }
 }
 
-#[derive(Default, Debug, Clone)]
-pub struct Cart {
-    path: Vec<Pos>,
-    forward_count: i32,
+#[derive(Default, Debug)]
+pub struct Cell {
     heatloss: i32,
-    direction: Direction,
 }
 
-#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Default, Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Direction {
     #[default]
     North,
@@ -68,117 +69,139 @@ pub enum Direction {
     West,
 }
 
-fn part1(data: &str) -> u64 {
-    0
-}
-
-pub struct App {
-    grid: Grid,
-    carts: Vec<Cart>,
-    best_path: Option<Vec<Pos>>,
-    best_heatloss: Option<i32>,
-    start: Pos,
-    end: Pos,
-}
+impl Direction {
+    pub fn delta(&self) -> Pos {
+        match self {
+            Direction::North => (0, -1),
+            Direction::South => (0, 1),
+            Direction::East => (1, 0),
+            Direction::West => (-1, 0),
+        }
+        .into()
+    }
 
-impl Default for App {
-    fn default() -> Self {
-        let carts = vec![
-            Cart {
-                direction: Direction::East,
-                ..Cart::default()
-            },
-            Cart {
-                direction: Direction::South,
-                ..Cart::default()
-            },
-        ];
-        Self {
-            grid: Default::default(),
-            carts,
-            best_path: Default::default(),
-            best_heatloss: Default::default(),
-            start: Default::default(),
-            end: Default::default(),
+    pub fn turns(&self) -> [Direction; 2] {
+        match self {
+            Direction::North | Direction::South => [Direction::East, Direction::West],
+            Direction::East | Direction::West => [Direction::North, Direction::South],
         }
     }
 }
 
-impl Add for Pos {
-    type Output = Pos;
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Crucible {
+    min_run: i32,
+    max_run: i32,
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Pos {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
+impl Crucible {
+    pub const NORMAL: Crucible = Crucible {
+        min_run: 1,
+        max_run: 3,
+    };
+    pub const ULTRA: Crucible = Crucible {
+        min_run: 4,
+        max_run: 10,
+    };
 }
 
-impl App {
-    pub fn best_path_length(&self) -> i32 {
-        match &self.best_path {
-            Some(v) => v.len() as i32,
-            None => 0,
-        }
-    }
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
+struct State {
+    pos: Pos,
+    direction: Direction,
+    run: i32,
+}
 
-    pub fn update_state(&mut self) {
-        if let Some(next_cart) = self.next_cart() {
-        } else {
-            self.carts.pop();
-        }
-    }
-    pub fn move_forwards(&mut self, cart: &Cart) -> Option<Cart> {
-        if cart.forward_count == 3 {
-            return None;
-        }
-        let mut new_cart = cart.clone();
-        let pos = cart.path[cart.path.len() - 1];
-        let next_cell = pos
-            + (match cart.direction {
-                Direction::North => (0, -1),
-                Direction::South => (0, 1),
-                Direction::East => (1, 0),
-                Direction::West => (-1, 0),
-            })
-            .into();
-        if !pos.contained_by(self.grid.limits) {
-            return None;
-        }
-        let entry = self.grid.elements.get(&next_cell).unwrap();
-        new_cart.heatloss += entry.heatloss;
-        new_cart.path.push(next_cell);
-        new_cart.forward_count += 1;
-        Some(new_cart)
-    }
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Route {
+    heatloss: i32,
+    path: Vec<Pos>,
+}
 
-    pub fn next_cart(&mut self) -> Option<Cart> {
-        let len = self.carts.len();
-        let cart = self.carts[len - 1].clone();
-        for dir in [
-            Direction::North,
-            Direction::South,
-            Direction::East,
-            Direction::West,
-        ] {
-            if cart.direction == dir && cart.forward_count < 3 {
-                if let Some(new_cart) = self.move_forwards(&cart) {
-                    return Some(new_cart);
+impl Grid {
+    pub fn solve(&self, crucible: Crucible, start: Pos, end: Pos) -> Option<Route> {
+        let mut best: HashMap<State, i32> = HashMap::new();
+        let mut previous: HashMap<State, State> = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for direction in [Direction::East, Direction::South] {
+            let state = State {
+                pos: start,
+                direction,
+                run: 0,
+            };
+            best.insert(state, 0);
+            queue.push(Reverse((0, state)));
+        }
+        while let Some(Reverse((heatloss, state))) = queue.pop() {
+            if best.get(&state).is_some_and(|&e| e < heatloss) {
+                continue;
+            }
+            if state.pos == end && state.run >= crucible.min_run {
+                let mut path = vec![state.pos];
+                let mut current = state;
+                while let Some(&before) = previous.get(&current) {
+                    path.push(before.pos);
+                    current = before;
+                }
+                path.reverse();
+                return Some(Route { heatloss, path });
+            }
+            let mut moves = vec![];
+            if state.run < crucible.max_run {
+                moves.push((state.direction, state.run + 1));
+            }
+            if state.run >= crucible.min_run {
+                moves.extend(state.direction.turns().map(|e| (e, 1)));
+            }
+            for (direction, run) in moves {
+                let pos = state.pos + direction.delta();
+                let Some(cell) = self.elements.get(&pos) else {
+                    continue;
+                };
+                let next = State {
+                    pos,
+                    direction,
+                    run,
+                };
+                let cost = heatloss + cell.heatloss;
+                if best.get(&next).map_or(true, |&e| cost < e) {
+                    best.insert(next, cost);
+                    previous.insert(next, state);
+                    queue.push(Reverse((cost, next)));
                 }
             }
         }
         None
     }
 }
-fn part1_gui(data: &str) -> u64 {
+
+fn part1(data: &str) -> u64 {
+    let grid = parse::grid(data).unwrap().1;
+    let route = grid.solve(Crucible::NORMAL, (0, 0).into(), grid.limits);
+    route.map_or(0, |e| e.heatloss as u64)
+}
+
+fn part2(data: &str) -> u64 {
+    let grid = parse::grid(data).unwrap().1;
+    let route = grid.solve(Crucible::ULTRA, (0, 0).into(), grid.limits);
+    route.map_or(0, |e| e.heatloss as u64)
+}
+
+pub struct App {
+    grid: Grid,
+    route: Route,
+    step: usize,
+}
+
+fn part1_gui(data: &str) {
     let grid = parse::grid(data).unwrap().1;
-    let limits = grid.limits;
+    let route = grid
+        .solve(Crucible::NORMAL, (0, 0).into(), grid.limits)
+        .unwrap_or_default();
     let app = App {
         grid,
-        start: (0, 0).into(),
-        end: limits,
-        ..App::default()
+        route,
+        step: 0,
     };
     let options = eframe::NativeOptions {
         initial_window_size: Some(egui::vec2(1600.0, 1600.0)),
@@ -186,22 +209,18 @@ fn part1_gui(data: &str) -> u64 {
     };
 
     eframe::run_native("AOC 2023 Day 17", options, Box::new(|_cc| Box::new(app)));
-    0
-}
-
-fn part2(data: &str) -> u64 {
-    0
 }
 
 fn main() {
-    part1_gui(include_str!("sample.txt"));
-    /*
+    if std::env::args().any(|e| e == "--gui") {
+        part1_gui(include_str!("sample.txt"));
+        return;
+    }
     println!("Day x of {}", version());
     let val = part1(input());
     println!("Part 1 answer is {}", val);
     let val = part2(input());
     println!("Part 2 answer is {}", val);
-    */
 }
 
 mod parse {
@@ -224,12 +243,10 @@ mod parse {
         let mut grid = Grid::default();
         for (y, line) in lines.iter().enumerate() {
             for (x, val) in line.iter().enumerate() {
-                let mut cell = Cell::default();
-                cell.heatloss = *val;
-                grid.elements.insert((x, y).into(), cell);
+                grid.elements.insert((x, y).into(), Cell { heatloss: *val });
             }
         }
-        grid.limits = (lines.len(), lines[0].len() - 1).into();
+        grid.limits = (lines[0].len() - 1, lines.len() - 1).into();
         Ok((i, grid))
     }
 }
@@ -249,6 +266,7 @@ mod tests {
             2 as i32,
             grid.elements.get(&(0, 0).into()).unwrap().heatloss
         );
+        assert_eq!(Pos::from((12, 12)), grid.limits);
     }
 
     #[test]
@@ -256,31 +274,52 @@ mod tests {
         assert_eq!(102, part1(sample()));
     }
 
-    /*
     #[test]
-    fn test_sample() {
-        assert_eq!(0, part1(sample()));
+    fn test_route() {
+        let grid = parse::grid(sample()).unwrap().1;
+        let route = grid
+            .solve(Crucible::NORMAL, (0, 0).into(), grid.limits)
+            .unwrap();
+        assert_eq!(Some(&Pos::from((0, 0))), route.path.first());
+        assert_eq!(Some(&grid.limits), route.path.last());
+        assert_eq!(
+            route.heatloss,
+            route.path[1..]
+                .iter()
+                .map(|e| grid.elements[e].heatloss)
+                .sum::<i32>()
+        );
     }
-    */
 
     #[test]
     fn test_sample_part_2() {
-        assert_eq!(0, part2(sample()));
+        assert_eq!(94, part2(sample()));
+        assert_eq!(
+            71,
+            part2(
+                "111111111111
+999999999991
+999999999991
+999999999991
+999999999991"
+            )
+        );
     }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::SidePanel::left("summary").show(ctx, |ui| {
-            ui.label(format!("best route is {:?} steps", self.best_path_length()));
             ui.label(format!(
-                "heatloss is {} steps",
-                self.best_heatloss.unwrap_or(-1)
+                "best route is {} steps",
+                self.route.path.len().saturating_sub(1)
             ));
+            ui.label(format!("heatloss is {}", self.route.heatloss));
+            ui.label(format!("showing step {}", self.step));
         });
         egui::CentralPanel::default().show(ctx, |ui| {
             let painter_size = egui::vec2(800.0, 800.0);
-            let (res, painter) = ui.allocate_painter(painter_size, Sense::hover());
+            let (_res, painter) = ui.allocate_painter(painter_size, Sense::hover());
             const SCALE: f32 = 32.0;
             let white_box = |painter: &Painter, pos: Pos| {
                 let min = Pos2 {
@@ -310,20 +349,20 @@ impl eframe::App for App {
             let yellow_line = |painter: &Painter, start: Pos, end: Pos| {
                 colored_line(painter, start, end, Color32::YELLOW)
             };
-            let red_line = |painter: &Painter, start: Pos, end: Pos| {
-                colored_line(painter, start, end, Color32::RED)
-            };
 
             ui.heading("Grid");
-            for (pos, cell) in self.grid.elements.iter() {
+            for pos in self.grid.elements.keys() {
                 white_box(&painter, *pos);
             }
-            yellow_line(&painter, (0, 0).into(), (2, 0).into());
-            yellow_line(&painter, (2, 0).into(), (2, 9).into());
-            red_line(&painter, (3, 0).into(), (3, 9).into());
+            let shown = (self.step + 1).min(self.route.path.len());
+            for pair in self.route.path[..shown].windows(2) {
+                yellow_line(&painter, pair[0], pair[1]);
+            }
 
-            ctx.request_repaint_after(Duration::from_millis(100));
-            self.update_state();
+            if self.step + 1 < self.route.path.len() {
+                self.step += 1;
+                ctx.request_repaint_after(Duration::from_millis(100));
+            }
         });
     }
 }
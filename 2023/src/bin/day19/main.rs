@@ -1,5 +1,6 @@
 use aoc_2023::aoc::*;
 use std::collections::HashMap;
+use std::fmt::Display;
 
 #[derive(Copy, Clone, Debug)]
 pub struct Part {
@@ -39,7 +40,34 @@ pub enum Rule {
     Default(Destination),
 }
 
+impl Display for Destination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rule(name) => write!(f, "{}", name),
+            Self::Accept => write!(f, "A"),
+            Self::Reject => write!(f, "R"),
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Gt(attr, val, d) => write!(f, "{}>{}:{}", attr, val, d),
+            Self::Lt(attr, val, d) => write!(f, "{}<{}:{}", attr, val, d),
+            Self::Eq(attr, val, d) => write!(f, "{}={}:{}", attr, val, d),
+            Self::Default(d) => write!(f, "{}", d),
+        }
+    }
+}
+
 impl Rule {
+    pub fn destination(&self) -> &Destination {
+        match self {
+            Self::Gt(_, _, d) | Self::Lt(_, _, d) | Self::Eq(_, _, d) | Self::Default(d) => d,
+        }
+    }
+
     pub fn matches(&self, part: &Part) -> Option<Destination> {
         match self {
             Self::Default(d) => Some(d.clone()),
@@ -72,43 +100,203 @@ fn input() -> &'static str {
     include_str!("input.txt")
 }
 
-fn process(map: &HashMap<String, Vec<Rule>>, part: &Part) -> Destination {
-    let mut rules = map.get("in").unwrap();
-    loop {
-        for rule in rules {
-            println!("Checking {:?} against {:?}", &part, &rule);
-            match rule.matches(part) {
-                Some(Destination::Accept) => return Destination::Accept,
-                Some(Destination::Reject) => return Destination::Reject,
-                Some(Destination::Rule(r)) => {
-                    println!("Jump to {}", &r);
-                    rules = map.get(&r).unwrap();
-                    break;
-                }
-                None => {}
+/// Inclusive ranges for x, m, a and s, empty when `lo > hi`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Cube([(u32, u32); 4]);
+
+impl Cube {
+    pub fn full() -> Self {
+        Self([(1, 4000); 4])
+    }
+
+    pub fn size(&self) -> u64 {
+        self.0
+            .iter()
+            .map(|&(lo, hi)| if lo > hi { 0 } else { (hi - lo + 1) as u64 })
+            .product()
+    }
+
+    fn with(&self, attr: usize, lo: u32, hi: u32) -> Self {
+        let mut ranges = self.0;
+        ranges[attr] = (ranges[attr].0.max(lo), ranges[attr].1.min(hi));
+        Self(ranges)
+    }
+
+    pub fn split(&self, rule: &Rule) -> (Cube, Vec<Cube>) {
+        match rule {
+            Rule::Gt(attr, val, _) => {
+                let attr = attr_index(attr);
+                (
+                    self.with(attr, val + 1, u32::MAX),
+                    vec![self.with(attr, 0, *val)],
+                )
+            }
+            Rule::Lt(attr, val, _) => {
+                let attr = attr_index(attr);
+                (
+                    self.with(attr, 0, val.saturating_sub(1)),
+                    vec![self.with(attr, *val, u32::MAX)],
+                )
+            }
+            Rule::Eq(attr, val, _) => {
+                let attr = attr_index(attr);
+                (
+                    self.with(attr, *val, *val),
+                    vec![
+                        self.with(attr, 0, val.saturating_sub(1)),
+                        self.with(attr, val + 1, u32::MAX),
+                    ],
+                )
             }
+            Rule::Default(_) => (*self, vec![]),
         }
     }
 }
 
-fn part1(data: &str) -> u64 {
-    let (map, parts) = parse::input(data).unwrap().1;
-    let mut accepted = vec![];
-    let mut rejected = vec![];
-
-    for part in parts {
-        if let Destination::Accept = process(&map, &part) {
-            accepted.push(part);
-        } else {
-            rejected.push(part);
+fn attr_index(attr: &str) -> usize {
+    match attr {
+        "x" => 0,
+        "m" => 1,
+        "a" => 2,
+        "s" => 3,
+        _ => panic!("bad attr"),
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Node {
+    Accept,
+    Reject,
+    Rule {
+        workflow: String,
+        index: usize,
+        rule: Rule,
+        then: Box<Node>,
+        otherwise: Option<Box<Node>>,
+    },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Step {
+    pub workflow: String,
+    pub index: usize,
+    pub rule: String,
+    pub matched: bool,
+}
+
+impl Node {
+    pub fn compile(map: &HashMap<String, Vec<Rule>>) -> Self {
+        Self::compile_rule(map, "in", 0, &mut vec![])
+    }
+
+    fn compile_rule(
+        map: &HashMap<String, Vec<Rule>>,
+        workflow: &str,
+        index: usize,
+        path: &mut Vec<String>,
+    ) -> Self {
+        if index == 0 {
+            if path.iter().any(|e| e == workflow) {
+                panic!("workflow {} loops back on itself", workflow);
+            }
+            path.push(workflow.to_owned());
+        }
+        let rules = map
+            .get(workflow)
+            .unwrap_or_else(|| panic!("no workflow {}", workflow));
+        let rule = rules[index].clone();
+        let then = Box::new(match rule.destination() {
+            Destination::Accept => Node::Accept,
+            Destination::Reject => Node::Reject,
+            Destination::Rule(next) => Self::compile_rule(map, next, 0, path),
+        });
+        let otherwise = match rule {
+            Rule::Default(_) => None,
+            _ => Some(Box::new(Self::compile_rule(map, workflow, index + 1, path))),
+        };
+        if index == 0 {
+            path.pop();
+        }
+        Node::Rule {
+            workflow: workflow.to_owned(),
+            index,
+            rule,
+            then,
+            otherwise,
+        }
+    }
+
+    pub fn accepts(&self, part: &Part) -> bool {
+        self.explain(part).0
+    }
+
+    pub fn explain(&self, part: &Part) -> (bool, Vec<Step>) {
+        let mut steps = vec![];
+        let mut node = self;
+        loop {
+            match node {
+                Node::Accept => return (true, steps),
+                Node::Reject => return (false, steps),
+                Node::Rule {
+                    workflow,
+                    index,
+                    rule,
+                    then,
+                    otherwise,
+                } => {
+                    let matched = rule.matches(part).is_some();
+                    steps.push(Step {
+                        workflow: workflow.clone(),
+                        index: *index,
+                        rule: rule.to_string(),
+                        matched,
+                    });
+                    node = match (matched, otherwise) {
+                        (true, _) | (false, None) => then,
+                        (false, Some(otherwise)) => otherwise,
+                    };
+                }
+            }
+        }
+    }
+
+    pub fn count(&self, cube: Cube) -> u64 {
+        if cube.size() == 0 {
+            return 0;
+        }
+        match self {
+            Node::Accept => cube.size(),
+            Node::Reject => 0,
+            Node::Rule {
+                rule,
+                then,
+                otherwise,
+                ..
+            } => {
+                let (matched, rest) = cube.split(rule);
+                let rest = match otherwise {
+                    Some(otherwise) => rest.into_iter().map(|e| otherwise.count(e)).sum(),
+                    None => 0,
+                };
+                then.count(matched) + rest
+            }
         }
     }
+}
 
-    accepted.iter().map(|e| e.value() as u64).sum()
+fn part1(data: &str) -> u64 {
+    let (map, parts) = parse::input(data).unwrap().1;
+    let tree = Node::compile(&map);
+    parts
+        .iter()
+        .filter(|e| tree.accepts(e))
+        .map(|e| e.value() as u64)
+        .sum()
 }
 
 fn part2(data: &str) -> u64 {
-    0
+    let (map, _) = parse::input(data).unwrap().1;
+    Node::compile(&map).count(Cube::full())
 }
 
 fn main() {
@@ -260,6 +448,45 @@ lnx{m>1548:A,A}",
 
     #[test]
     fn test_sample_part_2() {
-        assert_eq!(0, part2(sample()));
+        assert_eq!(167409079868000, part2(sample()));
+    }
+
+    #[test]
+    fn test_split() {
+        let rule = parse::rule_test("a<2006:qkq").unwrap().1;
+        let (matched, rest) = Cube::full().split(&rule);
+        assert_eq!(2005 * 4000 * 4000 * 4000, matched.size());
+        assert_eq!(1995 * 4000 * 4000 * 4000, rest[0].size());
+        let rule = parse::rule_test("x=10:A").unwrap().1;
+        let (matched, rest) = Cube::full().split(&rule);
+        assert_eq!(4000 * 4000 * 4000, matched.size());
+        assert_eq!(
+            3999 * 4000 * 4000 * 4000,
+            rest.iter().map(|e| e.size()).sum::<u64>()
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let (map, parts) = parse::input(sample()).unwrap().1;
+        let tree = Node::compile(&map);
+        let (accepted, steps) = tree.explain(&parts[0]);
+        assert!(accepted);
+        let route = steps
+            .iter()
+            .map(|e| format!("{}[{}] {} {}", e.workflow, e.index, e.rule, e.matched))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "in[0] s<1351:px false",
+                "in[1] qqz true",
+                "qqz[0] s>2770:qs true",
+                "qs[0] s>3448:A false",
+                "qs[1] lnx true",
+                "lnx[0] m>1548:A true",
+            ],
+            route
+        );
+        assert!(!tree.accepts(&parts[1]));
     }
 }
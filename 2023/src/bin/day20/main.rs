@@ -1,261 +1,36 @@
 use aoc_2023::aoc::*;
-use std::collections::{HashMap, VecDeque};
+use network::*;
+use std::collections::HashMap;
 
-#[derive(Eq, PartialEq, Clone, Debug)]
-pub struct Network {
-    components: HashMap<String, Component>,
-}
-
-#[derive(Eq, PartialEq, Clone, Debug)]
-pub enum Signal {
-    High(String),
-    Low(String),
-}
-
-#[derive(Eq, PartialEq, Copy, Clone, Debug)]
-pub enum State {
-    On,
-    Off,
-}
-
-#[derive(Eq, PartialEq, Clone, Debug)]
-pub enum Component {
-    Broadcaster(String, Vec<String>),
-    FlipFlop(String, State, Vec<String>),
-    Conjunction(String, HashMap<String, State>, Vec<String>),
-    Sink(String, u32, u32),
-    Button(String),
-}
+pub mod network;
 
 fn input() -> &'static str {
     include_str!("input.txt")
 }
 
-pub fn network_2() -> Network {
-    let mut network: HashMap<String, Component> = Default::default();
-    network.insert(
-        "broadcaster".to_owned(),
-        Component::Broadcaster("broadcaster".to_owned(), vec!["a".to_owned()]),
-    );
-    network.insert(
-        "a".to_owned(),
-        Component::FlipFlop(
-            "a".to_owned(),
-            State::Off,
-            vec!["inv".to_owned(), "con".to_owned()],
-        ),
-    );
-
-    let mut invmap = HashMap::default();
-    invmap.insert("a".to_owned(), State::Off);
-    network.insert(
-        "inv".to_owned(),
-        Component::Conjunction("inv".to_owned(), invmap, vec!["b".to_owned()]),
-    );
-    network.insert(
-        "b".to_owned(),
-        Component::FlipFlop("b".to_owned(), State::Off, vec!["con".to_owned()]),
-    );
-    let mut conmap = HashMap::default();
-    conmap.insert("a".to_owned(), State::Off);
-    conmap.insert("b".to_owned(), State::Off);
-    network.insert(
-        "con".to_owned(),
-        Component::Conjunction("con".to_owned(), conmap, vec!["output".to_owned()]),
-    );
-    network.insert(
-        "output".to_owned(),
-        Component::Sink("output".to_owned(), 0, 0),
-    );
-    Network {
-        components: network,
-    }
-}
-
-pub fn network_1() -> Network {
-    let mut network: HashMap<String, Component> = Default::default();
-    network.insert(
-        "broadcaster".to_owned(),
-        Component::Broadcaster(
-            "broadcaster".to_owned(),
-            vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
-        ),
-    );
-    network.insert(
-        "a".to_owned(),
-        Component::FlipFlop("a".to_owned(), State::Off, vec!["b".to_owned()]),
-    );
-    network.insert(
-        "b".to_owned(),
-        Component::FlipFlop("b".to_owned(), State::Off, vec!["c".to_owned()]),
-    );
-    network.insert(
-        "c".to_owned(),
-        Component::FlipFlop("c".to_owned(), State::Off, vec!["inv".to_owned()]),
-    );
-    let mut invmap = HashMap::new();
-    invmap.insert("c".to_owned(), State::Off);
-    network.insert(
-        "inv".to_owned(),
-        Component::Conjunction("inv".to_owned(), invmap, vec!["a".to_owned()]),
-    );
-    Network {
-        components: network,
-    }
-}
-
 fn part1(data: &str) -> u64 {
     let mut network = parse::network(data).unwrap().1;
-    let mut queue = VecDeque::new();
     let mut high = 0;
     let mut low = 0;
     for _ in 0..1000 {
-        queue.push_back(("broadcaster".to_owned(), Signal::Low("button".to_owned())));
-        while let Some((dest, signal)) = queue.pop_front() {
-            if signal.is_high() {
-                high += 1;
-            } else {
-                low += 1;
-            }
-            //println!("  Processing {:?} -> {:?}", signal, dest);
-            if let Some(component) = network.components.get_mut(&dest) {
-                for next in component.process(signal) {
-                    queue.push_back(next);
-                }
-            }
-        }
-        //println!("{:?}\n\n", network);
+        let (h, l) = network.press(|_, _| {});
+        high += h;
+        low += l;
     }
     println!("{} high, {} low.  result {}", high, low, high * low);
     high * low
 }
 
-impl Component {
-    pub fn name(&self) -> String {
-        match self {
-            Self::Broadcaster(name, _) => name.clone(),
-            Self::FlipFlop(name, _, _) => name.clone(),
-            Self::Conjunction(name, _, _) => name.clone(),
-            Self::Sink(name, _, _) => name.clone(),
-            Self::Button(name) => name.clone(),
-        }
-    }
-
-    pub fn targets(&self) -> Vec<String> {
-        match self {
-            Self::Broadcaster(_, targets) => targets.iter().cloned().collect(),
-            Self::FlipFlop(_, _, targets) => targets.iter().cloned().collect(),
-            Self::Conjunction(_, _, targets) => targets.iter().cloned().collect(),
-            Self::Sink(_, _, _) => vec![],
-            Self::Button(_) => vec![],
-        }
-    }
-
-    pub fn process(&mut self, signal: Signal) -> Vec<(String, Signal)> {
-        match self {
-            Self::FlipFlop(name, state, connections) => {
-                if let Signal::Low(_) = signal {
-                    if state == &State::On {
-                        *state = State::Off;
-                        connections
-                            .iter()
-                            .map(|e| (e.clone(), Signal::Low(name.clone())))
-                            .collect()
-                    } else {
-                        *state = State::On;
-                        connections
-                            .iter()
-                            .map(|e| (e.clone(), Signal::High(name.clone())))
-                            .collect()
-                    }
-                } else {
-                    vec![]
-                }
-            }
-            Self::Conjunction(name, map, connections) => {
-                //println!("{} - {:?}", &name, &map);
-                if let Signal::High(from) = signal {
-                    map.insert(from.clone(), State::On);
-                } else if let Signal::Low(from) = signal {
-                    map.insert(from.clone(), State::Off);
-                }
-                if map.iter().all(|e| e.1 == &State::On) {
-                    connections
-                        .iter()
-                        .map(|e| (e.clone(), Signal::Low(name.clone())))
-                        .collect()
-                } else {
-                    connections
-                        .iter()
-                        .map(|e| (e.clone(), Signal::High(name.clone())))
-                        .collect()
-                }
-            }
-            Self::Broadcaster(name, connections) => match signal {
-                Signal::High(_) => connections
-                    .iter()
-                    .map(|e| (e.clone(), Signal::High(name.clone())))
-                    .collect(),
-                Signal::Low(_) => connections
-                    .iter()
-                    .map(|e| (e.clone(), Signal::Low(name.clone())))
-                    .collect(),
-            },
-            Self::Button(_) => vec![],
-            Self::Sink(name, high, low) => {
-                //println!("Got {:?}", signal);
-                match signal {
-                    Signal::High(_) => *high += 1,
-                    Signal::Low(_) => *low += 1,
-                }
-                vec![]
-            }
-        }
-    }
-}
-
-impl Signal {
-    pub fn is_high(&self) -> bool {
-        match self {
-            Self::High(_) => true,
-            Self::Low(_) => false,
-        }
-    }
-}
-
 fn part2(data: &str) -> u64 {
-    let mut network = parse::network(data).unwrap().1;
-    let mut queue = VecDeque::new();
-    for i in 0..1000000000 {
-        queue.push_back(("broadcaster".to_owned(), Signal::Low("button".to_owned())));
-        let mut rx_low = 0;
-        let mut rx_high = 0;
-        while let Some((dest, signal)) = queue.pop_front() {
-            if dest == "rx" {
-                if signal.is_high() {
-                    rx_high += 1;
-                } else {
-                    println!("rx low @ {}", i);
-                    rx_low += 1;
-                }
-            }
-
-            //println!("  Processing {:?} -> {:?}", signal, dest);
-            if let Some(component) = network.components.get_mut(&dest) {
-                for next in component.process(signal) {
-                    queue.push_back(next);
-                }
-            }
-        }
-        if rx_high == 0 && rx_low == 1 {
-            return i;
-        }
-        //println!("{:?}\n\n", network);
-    }
-    0
+    let network = parse::network(data).unwrap().1;
+    network.presses_until_low("rx").unwrap_or(0)
 }
 
 fn main() {
+    if std::env::args().any(|e| e == "--dot") {
+        print!("{}", parse::network(input()).unwrap().1.dot());
+        return;
+    }
     println!("Day x of {}", version());
     let val = part1(input());
     println!("Part 1 answer is {}", val);
@@ -333,7 +108,7 @@ mod parse {
             }
         }
 
-        let network = Network { components };
+        let network = Network::new(components);
         Ok((i, network))
     }
 }
@@ -353,8 +128,27 @@ mod tests {
     #[test]
     fn test_parse_sample() {
         let network = parse::network(sample()).unwrap().1;
-        println!("{:?}", network);
+        assert_eq!(5, network.components.len());
+        assert_eq!(
+            Some(&Component::Conjunction(
+                "inv".to_owned(),
+                HashMap::from([("c".to_owned(), State::Off)]),
+                vec!["a".to_owned()]
+            )),
+            network.components.get("inv")
+        );
+        let network = parse::network(sample2()).unwrap().1;
+        assert_eq!(
+            Some(&Component::Conjunction(
+                "con".to_owned(),
+                HashMap::from([("a".to_owned(), State::Off), ("b".to_owned(), State::Off)]),
+                vec!["output".to_owned()]
+            )),
+            network.components.get("con")
+        );
+        assert_eq!(vec!["a", "b"], network.inputs("con"));
     }
+
     #[test]
     fn test_sample() {
         assert_eq!(32000000, part1(sample()));
@@ -369,4 +163,46 @@ mod tests {
     fn test_sample_part_2() {
         assert_eq!(0, part2(sample()));
     }
+
+    #[test]
+    fn test_sub_counters() {
+        let network = parse::network(sample2()).unwrap().1;
+        assert_eq!(None, network.sub_counters("output"));
+
+        let network = parse::network(input()).unwrap().1;
+        let counters = network.sub_counters("rx").unwrap();
+        assert_eq!(4, counters.feeds.len());
+        let periods = network.periods("rx").unwrap();
+        assert_eq!(counters.feeds, periods.keys().cloned().collect::<Vec<_>>());
+        assert_eq!(
+            vec![("ct", 3797), ("kp", 3733), ("ks", 3907), ("xc", 3823)],
+            periods
+                .iter()
+                .map(|(k, &v)| (k.as_str(), v))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(211712400442661), network.presses_until_low("rx"));
+    }
+
+    #[test]
+    fn test_dot() {
+        let network = parse::network(sample2()).unwrap().1;
+        assert_eq!(
+            "digraph network {
+    a [shape=box, label=\"%a\"];
+    b [shape=box, label=\"%b\"];
+    broadcaster [shape=doublecircle];
+    con [shape=diamond, label=\"&con\"];
+    inv [shape=diamond, label=\"&inv\"];
+    a -> inv;
+    a -> con;
+    b -> con;
+    broadcaster -> a;
+    con -> output;
+    inv -> b;
+}
+",
+            network.dot()
+        );
+    }
 }
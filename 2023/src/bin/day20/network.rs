@@ -0,0 +1,269 @@
+use num::integer::lcm;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+const PRESS_LIMIT: u64 = 1 << 20;
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Network {
+    pub components: HashMap<String, Component>,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Signal {
+    High(String),
+    Low(String),
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum State {
+    On,
+    Off,
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Component {
+    Broadcaster(String, Vec<String>),
+    FlipFlop(String, State, Vec<String>),
+    Conjunction(String, HashMap<String, State>, Vec<String>),
+    Sink(String, u32, u32),
+    Button(String),
+}
+
+impl Component {
+    pub fn name(&self) -> String {
+        match self {
+            Self::Broadcaster(name, _) => name.clone(),
+            Self::FlipFlop(name, _, _) => name.clone(),
+            Self::Conjunction(name, _, _) => name.clone(),
+            Self::Sink(name, _, _) => name.clone(),
+            Self::Button(name) => name.clone(),
+        }
+    }
+
+    pub fn targets(&self) -> Vec<String> {
+        match self {
+            Self::Broadcaster(_, targets) => targets.iter().cloned().collect(),
+            Self::FlipFlop(_, _, targets) => targets.iter().cloned().collect(),
+            Self::Conjunction(_, _, targets) => targets.iter().cloned().collect(),
+            Self::Sink(_, _, _) => vec![],
+            Self::Button(_) => vec![],
+        }
+    }
+
+    pub fn process(&mut self, signal: Signal) -> Vec<(String, Signal)> {
+        match self {
+            Self::FlipFlop(name, state, connections) => {
+                if let Signal::Low(_) = signal {
+                    if state == &State::On {
+                        *state = State::Off;
+                        connections
+                            .iter()
+                            .map(|e| (e.clone(), Signal::Low(name.clone())))
+                            .collect()
+                    } else {
+                        *state = State::On;
+                        connections
+                            .iter()
+                            .map(|e| (e.clone(), Signal::High(name.clone())))
+                            .collect()
+                    }
+                } else {
+                    vec![]
+                }
+            }
+            Self::Conjunction(name, map, connections) => {
+                //println!("{} - {:?}", &name, &map);
+                if let Signal::High(from) = signal {
+                    map.insert(from.clone(), State::On);
+                } else if let Signal::Low(from) = signal {
+                    map.insert(from.clone(), State::Off);
+                }
+                if map.iter().all(|e| e.1 == &State::On) {
+                    connections
+                        .iter()
+                        .map(|e| (e.clone(), Signal::Low(name.clone())))
+                        .collect()
+                } else {
+                    connections
+                        .iter()
+                        .map(|e| (e.clone(), Signal::High(name.clone())))
+                        .collect()
+                }
+            }
+            Self::Broadcaster(name, connections) => match signal {
+                Signal::High(_) => connections
+                    .iter()
+                    .map(|e| (e.clone(), Signal::High(name.clone())))
+                    .collect(),
+                Signal::Low(_) => connections
+                    .iter()
+                    .map(|e| (e.clone(), Signal::Low(name.clone())))
+                    .collect(),
+            },
+            Self::Button(_) => vec![],
+            Self::Sink(name, high, low) => {
+                //println!("Got {:?}", signal);
+                match signal {
+                    Signal::High(_) => *high += 1,
+                    Signal::Low(_) => *low += 1,
+                }
+                vec![]
+            }
+        }
+    }
+}
+
+impl Signal {
+    pub fn is_high(&self) -> bool {
+        match self {
+            Self::High(_) => true,
+            Self::Low(_) => false,
+        }
+    }
+}
+
+impl Network {
+    pub fn new(components: HashMap<String, Component>) -> Self {
+        Self { components }
+    }
+
+    /// Returns the number of high and low pulses sent.
+    pub fn press<F>(&mut self, mut observe: F) -> (u64, u64)
+    where
+        F: FnMut(&str, &Signal),
+    {
+        let mut queue = VecDeque::new();
+        let mut high = 0;
+        let mut low = 0;
+        queue.push_back(("broadcaster".to_owned(), Signal::Low("button".to_owned())));
+        while let Some((dest, signal)) = queue.pop_front() {
+            if signal.is_high() {
+                high += 1;
+            } else {
+                low += 1;
+            }
+            observe(&dest, &signal);
+            if let Some(component) = self.components.get_mut(&dest) {
+                for next in component.process(signal) {
+                    queue.push_back(next);
+                }
+            }
+        }
+        (high, low)
+    }
+
+    pub fn inputs(&self, name: &str) -> Vec<String> {
+        let mut inputs = self
+            .components
+            .values()
+            .filter(|e| e.targets().iter().any(|t| t == name))
+            .map(|e| e.name())
+            .collect::<Vec<_>>();
+        inputs.sort();
+        inputs
+    }
+
+    /// Excludes the broadcaster, which every component shares.
+    pub fn upstream(&self, name: &str) -> HashSet<String> {
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([name.to_owned()]);
+        while let Some(current) = queue.pop_front() {
+            for input in self.inputs(&current) {
+                if input != "broadcaster" && seen.insert(input.clone()) {
+                    queue.push_back(input);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Requires each input of the hub to be fed by a disjoint part of the network.
+    pub fn sub_counters(&self, output: &str) -> Option<SubCounters> {
+        let [hub] = &self.inputs(output)[..] else {
+            return None;
+        };
+        if !matches!(self.components.get(hub), Some(Component::Conjunction(..))) {
+            return None;
+        }
+        let feeds = self.inputs(hub);
+        let mut claimed = HashSet::new();
+        for feed in feeds.iter() {
+            let mut parts = self.upstream(feed);
+            parts.insert(feed.clone());
+            if parts.contains(hub) || !parts.is_disjoint(&claimed) {
+                return None;
+            }
+            claimed.extend(parts);
+        }
+        Some(SubCounters {
+            hub: hub.clone(),
+            feeds,
+        })
+    }
+
+    /// Checked by seeing each sub-counter fire again after twice as many presses.
+    pub fn periods(&self, output: &str) -> Option<BTreeMap<String, u64>> {
+        let counters = self.sub_counters(output)?;
+        let mut network = self.clone();
+        let mut fired: HashMap<String, Vec<u64>> = HashMap::new();
+        for press in 1..=PRESS_LIMIT {
+            network.press(|dest, signal| {
+                if let Signal::High(from) = signal {
+                    if dest == counters.hub {
+                        fired.entry(from.clone()).or_default().push(press);
+                    }
+                }
+            });
+            if counters
+                .feeds
+                .iter()
+                .all(|e| fired.get(e).is_some_and(|e| e.len() >= 2))
+            {
+                break;
+            }
+        }
+        counters
+            .feeds
+            .iter()
+            .map(|feed| {
+                let presses = fired.get(feed)?;
+                let first = *presses.first()?;
+                (presses.get(1) == Some(&(first * 2))).then(|| (feed.clone(), first))
+            })
+            .collect()
+    }
+
+    pub fn presses_until_low(&self, output: &str) -> Option<u64> {
+        self.periods(output)?.into_values().reduce(lcm)
+    }
+
+    pub fn dot(&self) -> String {
+        let mut names = self.components.keys().collect::<Vec<_>>();
+        names.sort();
+        let mut dot = "digraph network {\n".to_owned();
+        for name in names.iter() {
+            let node = match &self.components[*name] {
+                Component::Broadcaster(..) => format!("{} [shape=doublecircle]", name),
+                Component::FlipFlop(..) => format!("{} [shape=box, label=\"%{}\"]", name, name),
+                Component::Conjunction(..) => {
+                    format!("{} [shape=diamond, label=\"&{}\"]", name, name)
+                }
+                Component::Sink(..) | Component::Button(..) => name.to_string(),
+            };
+            dot.push_str(&format!("    {};\n", node));
+        }
+        for name in names.iter() {
+            for target in self.components[*name].targets() {
+                dot.push_str(&format!("    {} -> {};\n", name, target));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct SubCounters {
+    pub hub: String,
+    pub feeds: Vec<String>,
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+pub enum Category {
+    High,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+/// Compares by category, then card by card.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialOrd, PartialEq)]
+pub struct Hand {
+    pub category: Category,
+    pub strengths: Vec<usize>,
+    pub cards: String,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    pub order: Vec<char>,
+    pub wild: Vec<char>,
+    pub straights: bool,
+}
+
+impl Rules {
+    pub fn standard() -> Self {
+        Self {
+            order: "23456789TJQKA".chars().collect(),
+            wild: vec![],
+            straights: false,
+        }
+    }
+
+    pub fn jokers() -> Self {
+        Self {
+            order: "J23456789TQKA".chars().collect(),
+            wild: vec!['J'],
+            straights: false,
+        }
+    }
+
+    pub fn with_wild(mut self, wild: &str) -> Self {
+        self.wild.extend(wild.chars());
+        self
+    }
+
+    pub fn with_straights(mut self) -> Self {
+        self.straights = true;
+        self
+    }
+
+    pub fn strength(&self, c: char) -> usize {
+        self.order
+            .iter()
+            .position(|&e| e == c)
+            .unwrap_or_else(|| panic!("Bad card value: {}", c))
+    }
+
+    pub fn category(&self, cards: &str) -> Category {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        let mut wild = 0;
+        for c in cards.chars() {
+            if self.wild.contains(&c) {
+                wild += 1;
+            } else {
+                *counts.entry(c).or_default() += 1;
+            }
+        }
+        let mut sig: Vec<usize> = counts.values().copied().collect();
+        sig.sort_by(|a, b| b.cmp(a));
+        match sig.first_mut() {
+            Some(first) => *first += wild,
+            None => sig.push(wild),
+        }
+        let grouped = match sig[..] {
+            [5, ..] => Category::FiveOfAKind,
+            [4, ..] => Category::FourOfAKind,
+            [3, 2, ..] => Category::FullHouse,
+            [3, ..] => Category::ThreeOfAKind,
+            [2, 2, ..] => Category::TwoPair,
+            [2, ..] => Category::Pair,
+            _ => Category::High,
+        };
+        if self.straights && self.is_straight(&counts, wild) {
+            grouped.max(Category::Straight)
+        } else {
+            grouped
+        }
+    }
+
+    /// Held wild cards may only fill the gaps in a run of the full card order.
+    fn is_straight(&self, counts: &HashMap<char, usize>, wild: usize) -> bool {
+        if counts.values().any(|&e| e > 1) || self.order.len() < 5 {
+            return false;
+        }
+        let positions = counts.keys().map(|&c| self.strength(c)).collect::<Vec<_>>();
+        match (positions.iter().min(), positions.iter().max()) {
+            (Some(min), Some(max)) => {
+                let span = max - min + 1;
+                span <= 5 && span - positions.len() <= wild
+            }
+            _ => true,
+        }
+    }
+
+    pub fn hand(&self, cards: &str) -> Hand {
+        if cards.chars().count() != 5 {
+            panic!("Bad hand value: {}", cards);
+        }
+        Hand {
+            category: self.category(cards),
+            strengths: cards.chars().map(|e| self.strength(e)).collect(),
+            cards: cards.to_owned(),
+        }
+    }
+
+    pub fn rank<S: AsRef<str>>(&self, bids: &[(S, u64)]) -> Vec<(Hand, u64)> {
+        let mut ranked = bids
+            .iter()
+            .map(|(cards, bid)| (self.hand(cards.as_ref()), *bid))
+            .collect::<Vec<_>>();
+        ranked.sort();
+        ranked
+    }
+
+    pub fn winnings<S: AsRef<str>>(&self, bids: &[(S, u64)]) -> u64 {
+        self.rank(bids)
+            .iter()
+            .enumerate()
+            .map(|(i, (_, bid))| (i + 1) as u64 * bid)
+            .sum()
+    }
+}
@@ -1,6 +1,9 @@
 use aoc_2023::aoc::*;
+use navigator::*;
 use std::collections::HashMap;
 
+pub mod navigator;
+
 fn input() -> &'static str {
     include_str!("input.txt")
 }
@@ -27,13 +30,14 @@ fn get_count(directions: &str, map: &HashMap<String, (String, String)>, start: &
 
 fn part2(data: &str) -> u64 {
     let (directions, map) = parse::map(data).unwrap().1;
-    let locations = map.keys().filter(|e| e.ends_with('A')).collect::<Vec<_>>();
-    let counts = locations
-        .iter()
-        .map(|e| get_count(&directions, &map, e))
+    let starts = map
+        .keys()
+        .filter(|e| e.ends_with('A'))
+        .map(|e| e.as_str())
         .collect::<Vec<_>>();
-
-    counts.into_iter().reduce(num::integer::lcm).unwrap()
+    Navigator::new(&directions, &map)
+        .steps(&starts, |e| e.ends_with('Z'))
+        .unwrap()
 }
 
 fn main() {
@@ -61,7 +65,7 @@ mod parse {
         Ok((i, (key.to_owned(), (left.to_owned(), right.to_owned()))))
     }
 
-    pub fn map(i: &str) -> IResult<&str, (String, HashMap<String, (String, String)>)> {
+    pub fn map(i: &str) -> IResult<&str, (String, Network)> {
         let (i, directions) = terminated(alphanumeric1, line_ending)(i)?;
         let (i, _) = many0(line_ending)(i)?;
         let (i, lines) = many1(node)(i)?;
@@ -110,4 +114,59 @@ mod tests {
     fn test_sample_part_2() {
         assert_eq!(6, part2(sample3()));
     }
+
+    const OFFSET: &str = "L
+
+11A = (11B, 11B)
+11B = (11Z, 11Z)
+11Z = (11B, 11B)
+22A = (22Z, 22Z)
+22Z = (22B, 22B)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+33A = (33B, 33B)
+33B = (33B, 33B)
+44A = (44Z, 44Z)
+44Z = (44B, 44B)
+44B = (44B, 44B)
+55Z = (55B, 55B)
+55B = (55Z, 55Z)
+";
+
+    #[test]
+    fn test_trace() {
+        let (directions, map) = parse::map(OFFSET).unwrap().1;
+        let navigator = Navigator::new(&directions, &map);
+        let ghost = navigator.trace("11A", |e| e.ends_with('Z'));
+        assert_eq!((1, 2), (ghost.offset, ghost.period));
+        assert_eq!(vec![2], ghost.cycle_hits);
+        let ghost = navigator.trace("44A", |e| e.ends_with('Z'));
+        assert_eq!((2, 1), (ghost.offset, ghost.period));
+        assert_eq!(vec![1], ghost.prefix_hits);
+        assert!(ghost.cycle_hits.is_empty());
+        let ghost = navigator.trace("55Z", |e| e.ends_with('Z'));
+        assert_eq!((0, 2), (ghost.offset, ghost.period));
+        assert_eq!(vec![0], ghost.cycle_hits);
+    }
+
+    #[test]
+    fn test_steps() {
+        let (directions, map) = parse::map(OFFSET).unwrap().1;
+        let navigator = Navigator::new(&directions, &map);
+        let end = |e: &str| e.ends_with('Z');
+        // The first hits are 2 and 1, so the LCM shortcut would give 2.
+        assert_eq!(Some(4), navigator.steps(&["11A", "22A"], end));
+        assert_eq!(Some(1), navigator.steps(&["22A", "44A"], end));
+        assert_eq!(None, navigator.steps(&["11A", "44A"], end));
+        assert_eq!(None, navigator.steps(&["11A", "33A"], end));
+        assert_eq!(Some(2), navigator.steps(&["55Z"], end));
+        assert_eq!(Some(4), navigator.steps(&["55Z", "22A"], end));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((4, 6)), crt(0, 2, 1, 3));
+        assert_eq!(Some((10, 12)), crt(2, 4, 4, 6));
+        assert_eq!(None, crt(1, 4, 2, 6));
+    }
 }
@@ -0,0 +1,124 @@
+use num::Integer;
+use std::collections::HashMap;
+
+pub type Network = HashMap<String, (String, String)>;
+
+pub struct Navigator<'a> {
+    directions: Vec<char>,
+    network: &'a Network,
+}
+
+/// Hits before `offset` happen once; `cycle_hits` recur every `period` steps.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ghost {
+    pub offset: u64,
+    pub period: u64,
+    pub prefix_hits: Vec<u64>,
+    pub cycle_hits: Vec<u64>,
+}
+
+impl Ghost {
+    pub fn hits(&self, t: u64) -> bool {
+        if t < self.offset {
+            self.prefix_hits.contains(&t)
+        } else {
+            self.cycle_hits
+                .iter()
+                .any(|h| h % self.period == t % self.period)
+        }
+    }
+}
+
+impl<'a> Navigator<'a> {
+    pub fn new(directions: &str, network: &'a Network) -> Self {
+        Self {
+            directions: directions.chars().collect(),
+            network,
+        }
+    }
+
+    fn next(&self, location: &str, index: usize) -> &'a str {
+        let node = self.network.get(location).unwrap();
+        if self.directions[index] == 'L' {
+            &node.0
+        } else {
+            &node.1
+        }
+    }
+
+    pub fn trace(&self, start: &'a str, is_end: impl Fn(&str) -> bool) -> Ghost {
+        let mut seen: HashMap<(&str, usize), u64> = HashMap::new();
+        let mut hits = vec![];
+        let mut location = start;
+        let mut t = 0;
+        loop {
+            let index = t as usize % self.directions.len();
+            if let Some(&offset) = seen.get(&(location, index)) {
+                let (prefix_hits, cycle_hits) = hits.iter().partition(|&&h| h < offset);
+                return Ghost {
+                    offset,
+                    period: t - offset,
+                    prefix_hits,
+                    cycle_hits,
+                };
+            }
+            seen.insert((location, index), t);
+            if is_end(location) {
+                hits.push(t);
+            }
+            location = self.next(location, index);
+            t += 1;
+        }
+    }
+
+    pub fn steps(&self, starts: &[&'a str], is_end: impl Fn(&str) -> bool) -> Option<u64> {
+        let ghosts = starts
+            .iter()
+            .map(|e| self.trace(e, &is_end))
+            .collect::<Vec<_>>();
+        together(&ghosts)
+    }
+}
+
+/// CRT for moduli that need not be coprime.
+pub fn crt(a: i128, m: i128, b: i128, n: i128) -> Option<(i128, i128)> {
+    let e = m.extended_gcd(&n);
+    if (b - a) % e.gcd != 0 {
+        return None;
+    }
+    let lcm = m / e.gcd * n;
+    let k = ((b - a) / e.gcd * e.x).rem_euclid(n / e.gcd);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+pub fn together(ghosts: &[Ghost]) -> Option<u64> {
+    let latest = ghosts.iter().max_by_key(|e| e.offset)?;
+    // Until every ghost is cycling, only the last one's one-off hits can match.
+    if let Some(&t) = latest
+        .prefix_hits
+        .iter()
+        .find(|&&t| t > 0 && ghosts.iter().all(|g| g.hits(t)))
+    {
+        return Some(t);
+    }
+    // After that, line up one cycle hit from each ghost.
+    let mut residues = vec![(0i128, 1i128)];
+    for ghost in ghosts {
+        let period = ghost.period as i128;
+        residues = residues
+            .iter()
+            .flat_map(|&(r, m)| {
+                ghost
+                    .cycle_hits
+                    .iter()
+                    .filter_map(move |&h| crt(r, m, h as i128 % period, period))
+            })
+            .collect();
+    }
+    let offset = latest.offset.max(1) as i128;
+    residues
+        .into_iter()
+        .map(|(r, m)| r + ((offset - r).max(0) + m - 1) / m * m)
+        .min()
+        .map(|t| t as u64)
+}
@@ -0,0 +1,245 @@
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+
+pub trait BoxHasher<K> {
+    fn boxes(&self) -> usize;
+    fn hash(&self, key: &K) -> usize;
+}
+
+/// The puzzle's HASH algorithm over 256 boxes.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Holiday;
+
+pub fn holiday_hash(input: &str) -> usize {
+    input.bytes().fold(0, |a, e| (a + e as usize) * 17 % 256)
+}
+
+impl<K: AsRef<str>> BoxHasher<K> for Holiday {
+    fn boxes(&self) -> usize {
+        256
+    }
+
+    fn hash(&self, key: &K) -> usize {
+        holiday_hash(key.as_ref())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Standard<S = RandomState> {
+    boxes: usize,
+    state: S,
+}
+
+impl Standard {
+    pub fn new(boxes: usize) -> Self {
+        Self::with_state(boxes, RandomState::new())
+    }
+}
+
+impl<S> Standard<S> {
+    pub fn with_state(boxes: usize, state: S) -> Self {
+        assert!(boxes > 0, "Standard hasher needs at least one box");
+        Self { boxes, state }
+    }
+}
+
+impl<K: Hash, S: BuildHasher> BoxHasher<K> for Standard<S> {
+    fn boxes(&self) -> usize {
+        self.boxes
+    }
+
+    fn hash(&self, key: &K) -> usize {
+        (self.state.hash_one(key) % self.boxes as u64) as usize
+    }
+}
+
+/// Removal leaves a tombstone until a box holds more tombstones than entries.
+#[derive(Clone, Debug)]
+pub struct BoxMap<K, V, H = Holiday> {
+    hasher: H,
+    boxes: Vec<Vec<Option<(K, V)>>>,
+    dead: Vec<usize>,
+    index: HashMap<K, (usize, usize)>,
+}
+
+impl<K: Clone + Eq + Hash + AsRef<str>, V> BoxMap<K, V> {
+    pub fn new() -> Self {
+        Self::with_hasher(Holiday)
+    }
+}
+
+impl<K: Clone + Eq + Hash + AsRef<str>, V> Default for BoxMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V, H: BoxHasher<K>> BoxMap<K, V, H> {
+    pub fn with_hasher(hasher: H) -> Self {
+        let mut boxes = vec![];
+        boxes.resize_with(hasher.boxes(), Vec::new);
+        let dead = vec![0; boxes.len()];
+        Self {
+            hasher,
+            boxes,
+            dead,
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    pub fn tombstones(&self) -> usize {
+        self.dead.iter().sum()
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        let &(boxid, slot) = self.index.get(key)?;
+        self.boxes[boxid][slot].as_ref().map(|e| &e.1)
+    }
+
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(&(boxid, slot)) = self.index.get(&key) {
+            let entry = self.boxes[boxid][slot].as_mut().unwrap();
+            return Some(std::mem::replace(&mut entry.1, value));
+        }
+        let boxid = self.hasher.hash(&key);
+        self.index
+            .insert(key.clone(), (boxid, self.boxes[boxid].len()));
+        self.boxes[boxid].push(Some((key, value)));
+        None
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (boxid, slot) = self.index.remove(key)?;
+        let removed = self.boxes[boxid][slot].take().map(|e| e.1);
+        self.dead[boxid] += 1;
+        if self.dead[boxid] > self.boxes[boxid].len() - self.dead[boxid] {
+            self.compact(boxid);
+        }
+        removed
+    }
+
+    fn compact(&mut self, boxid: usize) {
+        self.boxes[boxid].retain(Option::is_some);
+        self.dead[boxid] = 0;
+        for (slot, (key, _)) in self.boxes[boxid].iter().flatten().enumerate() {
+            self.index.insert(key.clone(), (boxid, slot));
+        }
+    }
+
+    pub fn contents(&self, boxid: usize) -> impl Iterator<Item = (&K, &V)> {
+        self.boxes[boxid].iter().flatten().map(|(k, v)| (k, v))
+    }
+
+    /// Slots count live entries only.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &K, &V)> {
+        (0..self.boxes.len()).flat_map(move |boxid| {
+            self.contents(boxid)
+                .enumerate()
+                .map(move |(slot, (k, v))| (boxid, slot, k, v))
+        })
+    }
+}
+
+impl<K, V, H> Display for BoxMap<K, V, H>
+where
+    K: Clone + Eq + Hash + Display,
+    V: Display,
+    H: BoxHasher<K>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for boxid in 0..self.boxes.len() {
+            let mut contents = self.contents(boxid).peekable();
+            if contents.peek().is_none() {
+                continue;
+            }
+            write!(f, "Box {}:", boxid)?;
+            for (k, v) in contents {
+                write!(f, " [{} {}]", k, v)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_holiday_hash() {
+        assert_eq!(52, holiday_hash("HASH"));
+        assert_eq!(0, holiday_hash("rn"));
+        assert_eq!(3, holiday_hash("pc"));
+    }
+
+    #[test]
+    fn test_order() {
+        let mut map = BoxMap::new();
+        map.insert("rn", 1);
+        map.insert("cm", 2);
+        map.insert("qp", 3);
+        assert_eq!(Some(1), map.insert("rn", 4));
+        assert_eq!(Some(2), map.remove(&"cm"));
+        assert_eq!(None, map.remove(&"cm"));
+        map.insert("cm", 5);
+        assert_eq!(1, map.tombstones());
+        assert_eq!(3, map.len());
+        assert_eq!(Some(&5), map.get(&"cm"));
+        let entries = map.iter().collect::<Vec<_>>();
+        assert_eq!(
+            vec![(0, 0, &"rn", &4), (0, 1, &"cm", &5), (1, 0, &"qp", &3)],
+            entries
+        );
+        assert_eq!("Box 0: [rn 4] [cm 5]\nBox 1: [qp 3]\n", map.to_string());
+    }
+
+    #[test]
+    fn test_standard() {
+        let mut map = BoxMap::with_hasher(Standard::new(4));
+        for i in 0..100u32 {
+            map.insert(i, i * i);
+        }
+        for i in (0..100).step_by(2) {
+            map.remove(&i);
+        }
+        assert_eq!(50, map.len());
+        assert_eq!(Some(&81), map.get(&9));
+        assert_eq!(None, map.get(&8));
+        let mut keys = map.iter().map(|e| *e.2).collect::<Vec<_>>();
+        keys.sort();
+        assert_eq!((1..100).step_by(2).collect::<Vec<_>>(), keys);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one box")]
+    fn test_standard_without_boxes() {
+        Standard::new(0);
+    }
+
+    #[test]
+    fn test_compaction() {
+        let mut map = BoxMap::with_hasher(Standard::new(1));
+        for i in 0..1000u32 {
+            map.insert(i, i);
+            if i >= 3 {
+                map.remove(&(i - 3));
+            }
+        }
+        assert_eq!(3, map.len());
+        assert!(map.tombstones() <= map.len());
+        assert_eq!(Some(&998), map.get(&998));
+        let entries = map.iter().map(|e| (e.1, *e.2)).collect::<Vec<_>>();
+        assert_eq!(vec![(0, 997), (1, 998), (2, 999)], entries);
+    }
+}
@@ -1,3 +1,6 @@
+pub mod beam;
+pub mod boxmap;
+
 pub mod aoc {
     use nom::character::complete::char;
     use nom::character::complete::digit1;
@@ -1,5 +1,3 @@
-use std::fmt::Display;
-
 use miette::Diagnostic;
 use thiserror::Error;
 
@@ -8,11 +6,13 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
-    ParseError(#[from] std::num::ParseIntError),
-}
 
-impl Display for AocError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "an error")
-    }
+    #[error(transparent)]
+    #[diagnostic(code(aoc::parse_int_error))]
+    ParseIntError(#[from] std::num::ParseIntError),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] utils::error::ParseError),
 }
+
@@ -2,21 +2,21 @@ use itertools::Itertools;
 use nom::character::complete::{i32, newline, space1};
 use nom::multi::separated_list1;
 use nom::IResult;
+use nom_supreme::error::ErrorTree;
+use utils::parse::parse_all;
 
 use crate::custom_error::AocError;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    Ok(parse_input(input)
-        .unwrap()
-        .1
+    Ok(parse_all(parse_input, input)?
         .iter()
         .filter(|e| is_safe(e))
         .count()
         .to_string())
 }
 
-fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
+fn parse_input(input: &str) -> IResult<&str, Vec<Vec<i32>>, ErrorTree<&str>> {
     separated_list1(newline, separated_list1(space1, i32))(input)
 }
 
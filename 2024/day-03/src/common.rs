@@ -0,0 +1,227 @@
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Copy, Clone)]
+pub enum Action {
+    Enable,
+    Disable,
+    Accumulate(fn(&[i64]) -> i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Opcode {
+    pub name: &'static str,
+    pub arity: usize,
+    pub digits: RangeInclusive<usize>,
+    pub action: Action,
+}
+
+impl Opcode {
+    pub fn new(
+        name: &'static str,
+        arity: usize,
+        digits: RangeInclusive<usize>,
+        action: Action,
+    ) -> Self {
+        Self {
+            name,
+            arity,
+            digits,
+            action,
+        }
+    }
+
+    pub fn mul() -> Self {
+        Self::new(
+            "mul",
+            2,
+            1..=3,
+            Action::Accumulate(|args| args[0] * args[1]),
+        )
+    }
+
+    pub fn enable(name: &'static str) -> Self {
+        Self::new(name, 0, 0..=0, Action::Enable)
+    }
+
+    pub fn disable(name: &'static str) -> Self {
+        Self::new(name, 0, 0..=0, Action::Disable)
+    }
+
+    fn matches(&self, input: &[u8]) -> Option<(Vec<i64>, usize)> {
+        let mut pos = self.name.len();
+        if !input.starts_with(self.name.as_bytes()) || input.get(pos) != Some(&b'(') {
+            return None;
+        }
+        pos += 1;
+        let mut args = Vec::with_capacity(self.arity);
+        for i in 0..self.arity {
+            if i > 0 {
+                if input.get(pos) != Some(&b',') {
+                    return None;
+                }
+                pos += 1;
+            }
+            let len = input[pos..]
+                .iter()
+                .take_while(|e| e.is_ascii_digit())
+                .count();
+            if !self.digits.contains(&len) {
+                return None;
+            }
+            let arg = input[pos..pos + len].iter().try_fold(0_i64, |acc, e| {
+                acc.checked_mul(10)?.checked_add((e - b'0') as i64)
+            })?;
+            args.push(arg);
+            pos += len;
+        }
+        (input.get(pos) == Some(&b')')).then_some((args, pos + 1))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub offset: usize,
+    pub name: &'static str,
+    pub args: Vec<i64>,
+    pub value: Option<i64>,
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = self
+            .args
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(f, "@{} {}({})", self.offset, self.name, args)?;
+        if let Some(value) = self.value {
+            write!(f, " = {}", value)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Run {
+    pub total: i64,
+    pub steps: Vec<Step>,
+}
+
+pub struct Interpreter {
+    opcodes: Vec<Opcode>,
+}
+
+impl Interpreter {
+    pub fn new(opcodes: Vec<Opcode>) -> Self {
+        Self { opcodes }
+    }
+
+    pub fn run(&self, input: &str) -> Run {
+        let bytes = input.as_bytes();
+        let mut run = Run::default();
+        let mut enabled = true;
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let found = self
+                .opcodes
+                .iter()
+                .find_map(|op| op.matches(&bytes[offset..]).map(|e| (op, e)));
+            let Some((op, (args, len))) = found else {
+                offset += 1;
+                continue;
+            };
+            let value = match op.action {
+                Action::Enable => {
+                    enabled = true;
+                    None
+                }
+                Action::Disable => {
+                    enabled = false;
+                    None
+                }
+                Action::Accumulate(_) if !enabled => {
+                    offset += len;
+                    continue;
+                }
+                Action::Accumulate(f) => Some(f(&args)),
+            };
+            run.total += value.unwrap_or(0);
+            run.steps.push(Step {
+                offset,
+                name: op.name,
+                args,
+                value,
+            });
+            offset += len;
+        }
+        run
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_1: &str =
+        "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+    const SAMPLE_2: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    #[test]
+    fn test_mul_only() {
+        let run = Interpreter::new(vec![Opcode::mul()]).run(SAMPLE_1);
+        assert_eq!(161, run.total);
+        assert_eq!(
+            vec![1, 29, 53, 62],
+            run.steps.iter().map(|e| e.offset).collect::<Vec<_>>()
+        );
+        assert_eq!("@1 mul(2,4) = 8", run.steps[0].to_string());
+    }
+
+    #[test]
+    fn test_enabled_flag() {
+        let interpreter = Interpreter::new(vec![
+            Opcode::mul(),
+            Opcode::enable("do"),
+            Opcode::disable("don't"),
+        ]);
+        let run = interpreter.run(SAMPLE_2);
+        assert_eq!(48, run.total);
+        assert_eq!(
+            vec![
+                "@1 mul(2,4) = 8",
+                "@20 don't()",
+                "@59 do()",
+                "@64 mul(8,5) = 40"
+            ],
+            run.steps.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_operand_checks() {
+        let run = Interpreter::new(vec![Opcode::mul()])
+            .run("mul(1234,5)mul(12,)mul(,3)mul(2,3,4)mul(999,1)");
+        assert_eq!(999, run.total);
+        assert_eq!(1, run.steps.len());
+    }
+
+    #[test]
+    fn test_custom_opcode() {
+        let interpreter = Interpreter::new(vec![
+            Opcode::mul(),
+            Opcode::new(
+                "add",
+                3,
+                1..=2,
+                Action::Accumulate(|args| args.iter().sum()),
+            ),
+            Opcode::disable("halt"),
+        ]);
+        let run = interpreter.run("add(1,2,3)mul(2,2)add(1,2)halt()mul(5,5)");
+        assert_eq!(10, run.total);
+        assert_eq!("@10 mul(2,2) = 4", run.steps[1].to_string());
+    }
+}
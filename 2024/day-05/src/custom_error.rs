@@ -6,4 +6,15 @@ pub enum AocError {
     #[error(transparent)]
     #[diagnostic(code(aoc::io_error))]
     IoError(#[from] std::io::Error),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ParseError(#[from] utils::error::ParseError),
+
+    #[error("contradictory rules between pages {pages:?}")]
+    #[diagnostic(
+        code(aoc::contradictory_rules),
+        help("the rules for these pages form a cycle, so no order can satisfy them")
+    )]
+    ContradictoryRules { pages: Vec<i32> },
 }
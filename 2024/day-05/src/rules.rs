@@ -0,0 +1,222 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt::Display;
+
+use crate::custom_error::AocError;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Rule(pub i32, pub i32);
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Order(pub Vec<i32>);
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}|{}", self.0, self.1)
+    }
+}
+
+impl Order {
+    pub fn index_of(&self, val: i32) -> Option<usize> {
+        self.0.iter().position(|e| e == &val)
+    }
+
+    pub fn midpage(&self) -> i32 {
+        self.0[self.0.len() / 2]
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Rules {
+    before: HashMap<i32, HashSet<i32>>,
+}
+
+impl Rules {
+    pub fn new(rules: &[Rule]) -> Self {
+        let mut before: HashMap<i32, HashSet<i32>> = HashMap::new();
+        for rule in rules {
+            before.entry(rule.0).or_default().insert(rule.1);
+        }
+        Self { before }
+    }
+
+    pub fn must_precede(&self, a: i32, b: i32) -> bool {
+        self.before.get(&a).is_some_and(|e| e.contains(&b))
+    }
+
+    pub fn violation(&self, order: &Order) -> Option<Rule> {
+        order.0.iter().enumerate().find_map(|(i, &page)| {
+            order.0[..i]
+                .iter()
+                .find(|&&earlier| self.must_precede(page, earlier))
+                .map(|&earlier| Rule(page, earlier))
+        })
+    }
+
+    pub fn is_valid(&self, order: &Order) -> bool {
+        self.violation(order).is_none()
+    }
+
+    pub fn repair(&self, order: &Order) -> Result<Order, AocError> {
+        let pages = &order.0;
+        let mut incoming = vec![0; pages.len()];
+        for (i, &a) in pages.iter().enumerate() {
+            for (j, &b) in pages.iter().enumerate() {
+                if i != j && self.must_precede(a, b) {
+                    incoming[j] += 1;
+                }
+            }
+        }
+
+        let mut ready = (0..pages.len())
+            .filter(|&i| incoming[i] == 0)
+            .collect::<BTreeSet<_>>();
+        let mut result = Vec::with_capacity(pages.len());
+        while let Some(i) = ready.pop_first() {
+            result.push(pages[i]);
+            for (j, &b) in pages.iter().enumerate() {
+                if i != j && self.must_precede(pages[i], b) {
+                    incoming[j] -= 1;
+                    if incoming[j] == 0 {
+                        ready.insert(j);
+                    }
+                }
+            }
+        }
+
+        if result.len() < pages.len() {
+            let pages = self.cycle(pages, &incoming);
+            return Err(AocError::ContradictoryRules { pages });
+        }
+        Ok(Order(result))
+    }
+
+    /// Walks back along the unresolved rules until a page repeats.
+    fn cycle(&self, pages: &[i32], incoming: &[i32]) -> Vec<i32> {
+        let stuck = |i: usize| incoming[i] > 0;
+        let mut path: Vec<usize> = vec![];
+        let mut current = (0..pages.len()).find(|&i| stuck(i));
+        while let Some(i) = current {
+            if let Some(start) = path.iter().position(|&e| e == i) {
+                return path[start..].iter().rev().map(|&e| pages[e]).collect();
+            }
+            path.push(i);
+            current = (0..pages.len())
+                .find(|&j| j != i && stuck(j) && self.must_precede(pages[j], pages[i]));
+        }
+        vec![]
+    }
+}
+
+pub mod parser {
+    use super::*;
+    use nom::{
+        character::complete::line_ending, combinator::map, multi::many1, sequence::separated_pair,
+        IResult,
+    };
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::error::ParseError;
+    use utils::parse::{comma_separated_numbers, lines, number, parse_all};
+
+    pub fn parse(input: &str) -> Result<(Vec<Rule>, Vec<Order>), ParseError> {
+        parse_all(
+            separated_pair(lines(rule), many1(line_ending), lines(order)),
+            input,
+        )
+    }
+
+    fn rule(input: &str) -> IResult<&str, Rule, ErrorTree<&str>> {
+        map(separated_pair(number, tag("|"), number), |(a, b)| {
+            Rule(a, b)
+        })(input)
+    }
+
+    fn order(input: &str) -> IResult<&str, Order, ErrorTree<&str>> {
+        map(comma_separated_numbers, Order)(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13
+97|29\n53|29\n61|53\n97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13\n
+75,47,61,53,29\n97,61,53,29,13\n75,29,13\n75,97,47,61,53\n61,13,29\n97,13,75,29,47";
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let input = "47|53
+97|13
+97|61
+
+75,47,61,53,29
+97,61,53,29,13";
+        let (rules, orders) = parser::parse(input).unwrap();
+        assert_eq!(3, rules.len());
+        assert_eq!(2, orders.len());
+        assert_eq!(Rule(97, 13), rules[1]);
+        assert_eq!(Order(vec![75, 47, 61, 53, 29]), orders[0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_violation() -> miette::Result<()> {
+        let (rules, orders) = parser::parse(SAMPLE).unwrap();
+        let rules = Rules::new(&rules);
+        let violations = orders
+            .iter()
+            .map(|e| rules.violation(e))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                None,
+                None,
+                None,
+                Some(Rule(97, 75)),
+                Some(Rule(29, 13)),
+                Some(Rule(75, 13))
+            ],
+            violations
+        );
+        assert_eq!("97|75", Rule(97, 75).to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair() -> miette::Result<()> {
+        let (rules, orders) = parser::parse(SAMPLE).unwrap();
+        let rules = Rules::new(&rules);
+        assert_eq!(vec![97, 75, 47, 61, 53], rules.repair(&orders[3])?.0);
+        assert_eq!(vec![61, 29, 13], rules.repair(&orders[4])?.0);
+        assert_eq!(vec![97, 75, 47, 29, 13], rules.repair(&orders[5])?.0);
+        assert_eq!(orders[0], rules.repair(&orders[0])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_repair_ignores_absent_pages() -> miette::Result<()> {
+        let rules = Rules::new(&[Rule(1, 2), Rule(2, 3), Rule(3, 1)]);
+        assert_eq!(vec![1, 2], rules.repair(&Order(vec![2, 1]))?.0);
+        assert!(rules.is_valid(&Order(vec![3, 5, 1])));
+        Ok(())
+    }
+
+    #[test]
+    fn test_contradictory_rules() {
+        let rules = Rules::new(&[
+            Rule(1, 2),
+            Rule(2, 3),
+            Rule(3, 1),
+            Rule(4, 1),
+            Rule(1, 5),
+            Rule(5, 6),
+        ]);
+        let cycle = |order: Vec<i32>| match rules.repair(&Order(order)) {
+            Err(AocError::ContradictoryRules { pages }) => pages,
+            other => panic!("expected contradictory rules, got {:?}", other),
+        };
+        assert_eq!(vec![1, 2, 3], cycle(vec![3, 4, 2, 1]));
+        assert_eq!(vec![1, 2, 3], cycle(vec![3, 4, 6, 2, 5, 1]));
+        assert_eq!(vec![2, 3, 1], cycle(vec![6, 5, 3, 4, 2, 1]));
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 pub use std::fmt::Display;
 use utils::grid::*;
 
@@ -96,6 +97,146 @@ impl Map {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Jump {
+    pub steps: i32,
+    pub exits: bool,
+}
+
+#[derive(Debug)]
+pub struct Patrol {
+    width: i32,
+    height: i32,
+    start: Xy,
+    facing: Direction4,
+    jumps: Vec<[Jump; 4]>,
+}
+
+fn slot(dir: Direction4) -> usize {
+    match dir {
+        Direction4::N => 0,
+        Direction4::E => 1,
+        Direction4::S => 2,
+        Direction4::W => 3,
+    }
+}
+
+fn offset(pos: Xy, dir: Direction4, steps: i32) -> Xy {
+    let delta = dir.delta();
+    Xy::new(pos.x + delta.x * steps, pos.y + delta.y * steps)
+}
+
+impl Patrol {
+    pub fn new(map: &Map) -> Self {
+        let (width, height) = (map.grid.width(), map.grid.height());
+        let Content::Guard(start, facing) = map.guard else {
+            panic!("No guard on the map");
+        };
+        let blocked = |xy: Xy| map.grid.get(xy) == Some(&Content::Obstacle);
+        let mut jumps = vec![
+            [Jump {
+                steps: 0,
+                exits: true
+            }; 4];
+            (width * height) as usize
+        ];
+        for dir in Direction4::all() {
+            let delta = dir.delta();
+            let mut cells = map.grid.keys().collect::<Vec<_>>();
+            cells.sort_by_key(|e| -(e.x * delta.x + e.y * delta.y));
+            for cell in cells {
+                let ahead = cell + delta;
+                let jump = if !map.grid.in_bounds(ahead) {
+                    Jump {
+                        steps: 0,
+                        exits: true,
+                    }
+                } else if blocked(ahead) {
+                    Jump {
+                        steps: 0,
+                        exits: false,
+                    }
+                } else {
+                    let next = jumps[(ahead.y * width + ahead.x) as usize][slot(dir)];
+                    Jump {
+                        steps: next.steps + 1,
+                        ..next
+                    }
+                };
+                jumps[(cell.y * width + cell.x) as usize][slot(dir)] = jump;
+            }
+        }
+        Self {
+            width,
+            height,
+            start,
+            facing,
+            jumps,
+        }
+    }
+
+    fn jump(&self, pos: Xy, dir: Direction4, extra: Option<Xy>) -> Jump {
+        let jump = self.jumps[(pos.y * self.width + pos.x) as usize][slot(dir)];
+        let Some(extra) = extra else {
+            return jump;
+        };
+        let distance = match dir {
+            Direction4::N if extra.x == pos.x => pos.y - extra.y,
+            Direction4::S if extra.x == pos.x => extra.y - pos.y,
+            Direction4::E if extra.y == pos.y => extra.x - pos.x,
+            Direction4::W if extra.y == pos.y => pos.x - extra.x,
+            _ => return jump,
+        };
+        if distance > 0 && distance <= jump.steps {
+            Jump {
+                steps: distance - 1,
+                exits: false,
+            }
+        } else {
+            jump
+        }
+    }
+
+    fn walk(&self, extra: Option<Xy>, mut step: impl FnMut(Xy, Direction4, Jump)) -> bool {
+        let (mut pos, mut dir) = (self.start, self.facing);
+        let mut seen = HashSet::new();
+        loop {
+            let jump = self.jump(pos, dir, extra);
+            step(pos, dir, jump);
+            if jump.exits {
+                return false;
+            }
+            pos = offset(pos, dir, jump.steps);
+            if !seen.insert((pos, dir)) {
+                return true;
+            }
+            dir = dir.clockwise();
+        }
+    }
+
+    pub fn visited(&self) -> HashSet<Xy> {
+        let mut visited = HashSet::new();
+        self.walk(None, |pos, dir, jump| {
+            visited.extend((0..=jump.steps).map(|e| offset(pos, dir, e)));
+        });
+        visited
+    }
+
+    pub fn loops_with(&self, obstruction: Xy) -> bool {
+        obstruction != self.start
+            && (0..self.width).contains(&obstruction.x)
+            && (0..self.height).contains(&obstruction.y)
+            && self.walk(Some(obstruction), |_, _, _| {})
+    }
+
+    pub fn loop_positions(&self) -> HashSet<Xy> {
+        self.visited()
+            .into_par_iter()
+            .filter(|&e| self.loops_with(e))
+            .collect()
+    }
+}
+
 pub mod parser {
     use super::*;
     pub fn parse(input: &str) -> Map {
@@ -3,9 +3,9 @@ use crate::custom_error::AocError;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut map = parser::parse(input);
-    let visited = map.visited();
-    Ok((visited.keys().len()).to_string())
+    let map = parser::parse(input);
+    let patrol = Patrol::new(&map);
+    Ok(patrol.visited().len().to_string())
 }
 
 #[cfg(test)]
@@ -1,61 +1,18 @@
 use crate::common::*;
 use crate::custom_error::AocError;
-use std::collections::HashMap;
-use utils::grid::*;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut count = 0;
-    let mut map = parser::parse(input);
-    let guard = map.guard.clone();
-    let visited = map.visited();
-    map.guard = guard;
-    for c in map.grid.keys() {
-        let content = map.grid.get(c).cloned().unwrap();
-        let guard = map.guard.clone();
-        if visited.contains_key(&c) {
-            if process_candidate(&mut map, 0, 0, c) {
-                count += 1;
-            }
-            map.grid.insert(c, content);
-            map.guard = guard;
-        }
-    }
-
-    Ok(count.to_string())
-}
-
-pub fn process_candidate(map: &mut Map, _count: usize, _total: usize, pos: Xy) -> bool {
-    if let Content::Guard(p, _) = map.guard {
-        if p == pos {
-            return false;
-        }
-    }
-    if let Some(&Content::Obstacle) = map.grid.get(pos) {
-        return false;
-    }
-
-    map.grid.insert(pos, Content::Obstacle);
-    let mut visited = HashMap::new();
-    loop {
-        while map.next_cell() == Some(&Content::Empty) {
-            map.guard.move_cell();
-        }
-        let next = map.next_cell();
-        if next.is_none() {
-            return false;
-        }
-        if visited.contains_key(&map.guard) {
-            return true;
-        }
-        visited.insert(map.guard, ());
-        map.guard.turn_right();
-    }
+    let map = parser::parse(input);
+    let patrol = Patrol::new(&map);
+    Ok(patrol.loop_positions().len().to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+    use utils::grid::*;
 
     const SAMPLE: &str = "....#.....
 .........#
@@ -69,9 +26,23 @@ mod tests {
 ......#...";
 
     #[test]
-    fn test_process_candidate() -> miette::Result<()> {
-        let mut map = parser::parse(SAMPLE);
-        assert!(process_candidate(&mut map, 0, 0, Xy::new(3, 6)));
+    fn test_loops_with() -> miette::Result<()> {
+        let patrol = Patrol::new(&parser::parse(SAMPLE));
+        assert!(patrol.loops_with(Xy::new(3, 6)));
+        assert!(!patrol.loops_with(Xy::new(4, 6)));
+        assert!(!patrol.loops_with(Xy::new(5, 6)));
+        assert!(!patrol.loops_with(Xy::new(10, 6)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_loop_positions() -> miette::Result<()> {
+        let patrol = Patrol::new(&parser::parse(SAMPLE));
+        let expected = [(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)]
+            .into_iter()
+            .map(|(x, y)| Xy::new(x, y))
+            .collect::<HashSet<_>>();
+        assert_eq!(expected, patrol.loop_positions());
         Ok(())
     }
 
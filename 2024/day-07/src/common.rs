@@ -0,0 +1,354 @@
+pub trait Operator {
+    fn symbol(&self) -> &str;
+    fn apply(&self, a: i64, b: i64) -> Option<i64>;
+    fn invert(&self, result: i64, b: i64) -> Option<i64>;
+    /// Whether every left operand gives `result`, as `x * 0 == 0` does.
+    fn absorbs(&self, _result: i64, _b: i64) -> bool {
+        false
+    }
+}
+
+pub struct Add;
+pub struct Multiply;
+pub struct Concat;
+
+impl Operator for Add {
+    fn symbol(&self) -> &str {
+        "+"
+    }
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        a.checked_add(b)
+    }
+    fn invert(&self, result: i64, b: i64) -> Option<i64> {
+        result.checked_sub(b)
+    }
+}
+
+impl Operator for Multiply {
+    fn symbol(&self) -> &str {
+        "*"
+    }
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        a.checked_mul(b)
+    }
+    fn invert(&self, result: i64, b: i64) -> Option<i64> {
+        if result.checked_rem(b)? != 0 {
+            return None;
+        }
+        result.checked_div(b)
+    }
+    fn absorbs(&self, result: i64, b: i64) -> bool {
+        b == 0 && result == 0
+    }
+}
+
+impl Concat {
+    fn shift(b: i64) -> Option<i64> {
+        10_i64.checked_pow(b.checked_ilog10().unwrap_or(0) + 1)
+    }
+}
+
+impl Operator for Concat {
+    fn symbol(&self) -> &str {
+        "||"
+    }
+    fn apply(&self, a: i64, b: i64) -> Option<i64> {
+        if a < 0 || b < 0 {
+            return None;
+        }
+        a.checked_mul(Self::shift(b)?)?.checked_add(b)
+    }
+    fn invert(&self, result: i64, b: i64) -> Option<i64> {
+        if b < 0 || result < b {
+            return None;
+        }
+        let shift = Self::shift(b)?;
+        let head = result - b;
+        (head % shift == 0).then_some(head / shift)
+    }
+}
+
+pub fn standard() -> Vec<Box<dyn Operator>> {
+    vec![Box::new(Add), Box::new(Multiply)]
+}
+
+pub fn with_concat() -> Vec<Box<dyn Operator>> {
+    vec![Box::new(Add), Box::new(Multiply), Box::new(Concat)]
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Equation {
+    pub result: i64,
+    pub components: Vec<i64>,
+}
+
+impl Equation {
+    pub fn evaluate(&self, operators: &[&dyn Operator]) -> Option<i64> {
+        let (first, rest) = self.components.split_first()?;
+        if rest.len() != operators.len() {
+            return None;
+        }
+        rest.iter()
+            .zip(operators)
+            .try_fold(*first, |total, (&b, op)| op.apply(total, b))
+    }
+
+    pub fn describe(&self, operators: &[&dyn Operator]) -> String {
+        let mut result = format!("{}:", self.result);
+        let Some((first, rest)) = self.components.split_first() else {
+            return result;
+        };
+        result.push_str(&format!(" {}", first));
+        for (b, op) in rest.iter().zip(operators) {
+            result.push_str(&format!(" {} {}", op.symbol(), b));
+        }
+        result
+    }
+}
+
+pub struct Solver {
+    operators: Vec<Box<dyn Operator>>,
+}
+
+impl Solver {
+    pub fn new(operators: Vec<Box<dyn Operator>>) -> Self {
+        Self { operators }
+    }
+
+    pub fn solve(&self, equation: &Equation) -> Option<Vec<&dyn Operator>> {
+        let mut chosen = vec![];
+        if self.search(equation.result, &equation.components, &mut chosen) {
+            chosen.reverse();
+            Some(chosen)
+        } else {
+            None
+        }
+    }
+
+    pub fn can_make(&self, equation: &Equation) -> bool {
+        self.solve(equation).is_some()
+    }
+
+    fn search<'a>(
+        &'a self,
+        target: i64,
+        components: &[i64],
+        chosen: &mut Vec<&'a dyn Operator>,
+    ) -> bool {
+        match components {
+            [] => false,
+            [first] => *first == target,
+            [rest @ .., last] => {
+                for op in &self.operators {
+                    chosen.push(op.as_ref());
+                    let found = match op.invert(target, *last) {
+                        Some(previous) => self.search(previous, rest, chosen),
+                        None if op.absorbs(target, *last) => self.search_any(rest, chosen),
+                        None => false,
+                    };
+                    if found {
+                        return true;
+                    }
+                    chosen.pop();
+                }
+                false
+            }
+        }
+    }
+
+    fn search_any<'a>(&'a self, components: &[i64], chosen: &mut Vec<&'a dyn Operator>) -> bool {
+        let Some((first, rest)) = components.split_first() else {
+            return false;
+        };
+        let mut forward = vec![];
+        if !self.forward(*first, rest, &mut forward) {
+            return false;
+        }
+        chosen.extend(forward.into_iter().rev());
+        true
+    }
+
+    fn forward<'a>(&'a self, total: i64, rest: &[i64], chosen: &mut Vec<&'a dyn Operator>) -> bool {
+        let Some((b, rest)) = rest.split_first() else {
+            return true;
+        };
+        for op in &self.operators {
+            let Some(total) = op.apply(total, *b) else {
+                continue;
+            };
+            chosen.push(op.as_ref());
+            if self.forward(total, rest, chosen) {
+                return true;
+            }
+            chosen.pop();
+        }
+        false
+    }
+}
+
+pub mod parser {
+    use super::*;
+    use nom::{combinator::map, IResult};
+    use nom_supreme::error::ErrorTree;
+    use utils::error::ParseError;
+    use utils::parse::{key_value, lines, number, parse_all, space_separated_numbers};
+
+    pub fn parse(input: &str) -> Result<Vec<Equation>, ParseError> {
+        parse_all(lines(equation), input)
+    }
+
+    fn equation(input: &str) -> IResult<&str, Equation, ErrorTree<&str>> {
+        map(
+            key_value(number, space_separated_numbers),
+            |(result, components)| Equation { result, components },
+        )(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    struct Subtract;
+    struct Xor;
+
+    impl Operator for Subtract {
+        fn symbol(&self) -> &str {
+            "-"
+        }
+        fn apply(&self, a: i64, b: i64) -> Option<i64> {
+            a.checked_sub(b)
+        }
+        fn invert(&self, result: i64, b: i64) -> Option<i64> {
+            result.checked_add(b)
+        }
+    }
+
+    impl Operator for Xor {
+        fn symbol(&self) -> &str {
+            "^"
+        }
+        fn apply(&self, a: i64, b: i64) -> Option<i64> {
+            Some(a ^ b)
+        }
+        fn invert(&self, result: i64, b: i64) -> Option<i64> {
+            Some(result ^ b)
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let eqs = parser::parse(SAMPLE).unwrap();
+        assert_eq!(9, eqs.len());
+        assert_eq!(3267, eqs[1].result);
+        assert_eq!(vec![17, 5], eqs[2].components);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(Some(156), Concat.apply(15, 6));
+        assert_eq!(Some(100), Concat.apply(10, 0));
+        assert_eq!(Some(15), Concat.invert(156, 6));
+        assert_eq!(Some(10), Concat.invert(100, 0));
+        assert_eq!(None, Concat.invert(156, 5));
+        assert_eq!(None, Concat.invert(6, 16));
+    }
+
+    #[test]
+    fn test_multiply_invert() {
+        assert_eq!(Some(4), Multiply.invert(12, 3));
+        assert_eq!(None, Multiply.invert(13, 3));
+        assert_eq!(None, Multiply.invert(0, 0));
+        assert_eq!(None, Multiply.invert(i64::MIN, -1));
+        assert!(Multiply.absorbs(0, 0));
+        assert!(!Multiply.absorbs(5, 0));
+    }
+
+    #[test]
+    fn test_zero_operand() {
+        let solver = Solver::new(standard());
+        let eq = Equation {
+            result: 0,
+            components: vec![5, 0],
+        };
+        let ops = solver.solve(&eq).unwrap();
+        assert_eq!("0: 5 * 0", eq.describe(&ops));
+
+        let eq = Equation {
+            result: 0,
+            components: vec![3, 4, 0],
+        };
+        let ops = solver.solve(&eq).unwrap();
+        assert_eq!("0: 3 + 4 * 0", eq.describe(&ops));
+        assert_eq!(Some(0), eq.evaluate(&ops));
+
+        let eq = Equation {
+            result: 7,
+            components: vec![3, 4, 0],
+        };
+        let ops = solver.solve(&eq).unwrap();
+        assert_eq!("7: 3 + 4 + 0", eq.describe(&ops));
+    }
+
+    #[test]
+    fn test_solve() {
+        let eqs = parser::parse(SAMPLE).unwrap();
+        let solver = Solver::new(standard());
+        let ops = solver.solve(&eqs[1]).unwrap();
+        assert_eq!("3267: 81 * 40 + 27", eqs[1].describe(&ops));
+        assert_eq!(Some(3267), eqs[1].evaluate(&ops));
+        assert!(solver.solve(&eqs[2]).is_none());
+        assert!(!solver.can_make(&eqs[3]));
+
+        let solver = Solver::new(with_concat());
+        let ops = solver.solve(&eqs[4]).unwrap();
+        assert_eq!("7290: 6 * 8 || 6 * 15", eqs[4].describe(&ops));
+        assert_eq!(Some(7290), eqs[4].evaluate(&ops));
+    }
+
+    #[test]
+    fn test_every_solution_evaluates() {
+        let solver = Solver::new(with_concat());
+        for eq in parser::parse(SAMPLE).unwrap() {
+            if let Some(ops) = solver.solve(&eq) {
+                assert_eq!(Some(eq.result), eq.evaluate(&ops));
+            }
+        }
+    }
+
+    #[test]
+    fn test_user_operators() {
+        let solver = Solver::new(vec![Box::new(Add), Box::new(Subtract), Box::new(Xor)]);
+        let eq = Equation {
+            result: 1,
+            components: vec![2, 3, 2],
+        };
+        let ops = solver.solve(&eq).unwrap();
+        assert_eq!("1: 2 - 3 + 2", eq.describe(&ops));
+
+        let eq = Equation {
+            result: 5,
+            components: vec![12, 9],
+        };
+        let ops = solver.solve(&eq).unwrap();
+        assert_eq!("5: 12 ^ 9", eq.describe(&ops));
+        assert!(Solver::new(standard()).solve(&eq).is_none());
+
+        let empty = Equation {
+            result: 5,
+            components: vec![],
+        };
+        assert_eq!("5:", empty.describe(&[]));
+        assert!(solver.solve(&empty).is_none());
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt::Display;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Strategy {
+    Blocks,
+    Files,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Run {
+    pub fn new(start: usize, len: usize) -> Self {
+        Self { start, len }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpanTree {
+    size: usize,
+    max: Vec<usize>,
+}
+
+impl SpanTree {
+    pub fn new(lengths: &[usize]) -> Self {
+        let size = lengths.len().next_power_of_two();
+        let mut max = vec![0; 2 * size];
+        max[size..size + lengths.len()].copy_from_slice(lengths);
+        for i in (1..size).rev() {
+            max[i] = max[2 * i].max(max[2 * i + 1]);
+        }
+        Self { size, max }
+    }
+
+    pub fn update(&mut self, index: usize, len: usize) {
+        let mut i = index + self.size;
+        self.max[i] = len;
+        while i > 1 {
+            i /= 2;
+            self.max[i] = self.max[2 * i].max(self.max[2 * i + 1]);
+        }
+    }
+
+    pub fn first_fit(&self, need: usize, before: usize) -> Option<usize> {
+        self.first_fit_impl(1, 0, self.size, need, before)
+    }
+
+    fn first_fit_impl(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        need: usize,
+        before: usize,
+    ) -> Option<usize> {
+        if lo >= before || self.max[node] < need {
+            return None;
+        }
+        if hi - lo == 1 {
+            return Some(lo);
+        }
+        let mid = (lo + hi) / 2;
+        self.first_fit_impl(2 * node, lo, mid, need, before)
+            .or_else(|| self.first_fit_impl(2 * node + 1, mid, hi, need, before))
+    }
+}
+
+#[derive(Debug)]
+pub struct Disk {
+    files: Vec<Vec<Run>>,
+    gaps: Vec<Run>,
+    index: SpanTree,
+    size: usize,
+}
+
+impl Disk {
+    pub fn new(files: Vec<Run>, gaps: Vec<Run>) -> Self {
+        let size = files
+            .iter()
+            .chain(gaps.iter())
+            .map(|e| e.start + e.len)
+            .max()
+            .unwrap_or(0);
+        let index = SpanTree::new(&gaps.iter().map(|e| e.len).collect::<Vec<_>>());
+        Self {
+            files: files.into_iter().map(|e| vec![e]).collect(),
+            gaps,
+            index,
+            size,
+        }
+    }
+
+    fn take_gap(&mut self, gap: usize, len: usize) -> Run {
+        let run = Run::new(self.gaps[gap].start, len);
+        self.gaps[gap].start += len;
+        self.gaps[gap].len -= len;
+        self.index.update(gap, self.gaps[gap].len);
+        run
+    }
+
+    fn move_blocks(&mut self, id: usize) {
+        let Some(mut run) = self.files[id].pop() else {
+            return;
+        };
+        let mut runs = std::mem::take(&mut self.files[id]);
+        while run.len > 0 {
+            let Some(gap) = self.index.first_fit(1, id) else {
+                break;
+            };
+            let len = run.len.min(self.gaps[gap].len);
+            runs.push(self.take_gap(gap, len));
+            run.len -= len;
+        }
+        if run.len > 0 {
+            runs.push(run);
+        }
+        self.files[id] = runs;
+    }
+
+    fn move_file(&mut self, id: usize) {
+        let len = self.files[id].iter().map(|e| e.len).sum();
+        if let Some(gap) = self.index.first_fit(len, id) {
+            self.files[id] = vec![self.take_gap(gap, len)];
+        }
+    }
+
+    pub fn compact(&mut self, strategy: Strategy) {
+        for id in (0..self.files.len()).rev() {
+            match strategy {
+                Strategy::Blocks => self.move_blocks(id),
+                Strategy::Files => self.move_file(id),
+            }
+        }
+    }
+
+    pub fn checksum(&self) -> u64 {
+        self.files
+            .iter()
+            .enumerate()
+            .flat_map(|(id, runs)| runs.iter().map(move |run| (id, run)))
+            .map(|(id, run)| {
+                let (start, len) = (run.start as u64, run.len as u64);
+                id as u64 * (start * len + len * len.saturating_sub(1) / 2)
+            })
+            .sum()
+    }
+}
+
+impl Display for Disk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut layout = vec!['.'; self.size];
+        for (id, runs) in self.files.iter().enumerate() {
+            let c = char::from_digit(id as u32, 10).unwrap_or('#');
+            for run in runs {
+                layout[run.start..run.start + run.len].fill(c);
+            }
+        }
+        write!(f, "{}", layout.into_iter().collect::<String>())
+    }
+}
+
+pub mod parser {
+    use super::*;
+
+    pub fn parse(input: &str) -> Disk {
+        let mut files = vec![];
+        let mut gaps = vec![];
+        let mut start = 0;
+        for (i, c) in input.trim().chars().enumerate() {
+            let len = c.to_digit(10).expect("can't parse c") as usize;
+            if i % 2 == 0 {
+                files.push(Run::new(start, len));
+            } else {
+                gaps.push(Run::new(start, len));
+            }
+            start += len;
+        }
+        Disk::new(files, gaps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "2333133121414131402";
+
+    #[test]
+    fn test_parse() {
+        let disk = parser::parse(SAMPLE);
+        assert_eq!(
+            "00...111...2...333.44.5555.6666.777.888899",
+            disk.to_string()
+        );
+        assert_eq!("0..111....22222", parser::parse("12345").to_string());
+    }
+
+    #[test]
+    fn test_compact_blocks() {
+        let mut disk = parser::parse(SAMPLE);
+        disk.compact(Strategy::Blocks);
+        assert_eq!(
+            "0099811188827773336446555566..............",
+            disk.to_string()
+        );
+        assert_eq!(1928, disk.checksum());
+
+        let mut disk = parser::parse("12345");
+        disk.compact(Strategy::Blocks);
+        assert_eq!("022111222......", disk.to_string());
+    }
+
+    #[test]
+    fn test_compact_files() {
+        let mut disk = parser::parse(SAMPLE);
+        disk.compact(Strategy::Files);
+        assert_eq!(
+            "00992111777.44.333....5555.6666.....8888..",
+            disk.to_string()
+        );
+        assert_eq!(2858, disk.checksum());
+    }
+
+    #[test]
+    fn test_checksum() {
+        let disk = Disk::new(vec![Run::new(0, 2), Run::new(5, 1)], vec![Run::new(2, 3)]);
+        assert_eq!(5, disk.checksum());
+    }
+
+    #[test]
+    fn test_span_tree() {
+        let mut tree = SpanTree::new(&[1, 0, 3, 2, 5]);
+        assert_eq!(Some(0), tree.first_fit(1, 5));
+        assert_eq!(Some(2), tree.first_fit(2, 5));
+        assert_eq!(Some(4), tree.first_fit(4, 5));
+        assert_eq!(None, tree.first_fit(4, 4));
+        assert_eq!(None, tree.first_fit(6, 5));
+        tree.update(2, 0);
+        assert_eq!(Some(3), tree.first_fit(2, 5));
+    }
+}
@@ -2,7 +2,7 @@ use crate::custom_error::AocError;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let stones = parser::parse(input);
+    let stones = parser::parse(input)?;
     let result = (0..25).fold(stones, |acc, _i| {
         acc.iter().flat_map(|&e| step(e)).collect::<Vec<_>>()
     });
@@ -25,11 +25,12 @@ pub fn step(stone: u64) -> Vec<u64> {
 }
 
 pub mod parser {
-    use nom::{bytes::complete::tag, character::complete::u64, multi::separated_list1};
+    use nom::{character::complete::u64, multi::separated_list1};
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
 
-    pub fn parse(input: &str) -> Vec<u64> {
-        let (_, result) = separated_list1(tag(" "), u64::<&str, ()>)(input).unwrap();
-        result
+    pub fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+        parse_all(separated_list1(tag(" "), u64::<&str, ErrorTree<&str>>), input)
     }
 }
 #[cfg(test)]
@@ -46,7 +47,9 @@ mod tests {
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        assert_eq!(vec![125, 17], parser::parse(SAMPLE));
+        assert_eq!(vec![125, 17], parser::parse(SAMPLE)?);
+        let err = parser::parse("125 x7").unwrap_err();
+        assert_eq!((1, 5), (err.line, err.column));
         Ok(())
     }
 
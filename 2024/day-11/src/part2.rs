@@ -3,7 +3,7 @@ use std::collections::HashMap;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let stones = parser::parse(input);
+    let stones = parser::parse(input)?;
     let mut memo = Stones::new();
     let result: usize = stones.iter().map(|&e| memo.expand(e, 75)).sum();
     Ok(result.to_string())
@@ -56,14 +56,15 @@ impl Stones {
 }
 
 pub mod parser {
-    use nom::{
-        bytes::complete::tag, character::complete::u64, combinator::map, multi::separated_list1,
-    };
+    use nom::{character::complete::u64, combinator::map, multi::separated_list1};
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
 
-    pub fn parse(input: &str) -> Vec<usize> {
-        let (_, result) =
-            separated_list1(tag(" "), map(u64::<&str, ()>, |e| e as usize))(input).unwrap();
-        result
+    pub fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        parse_all(
+            separated_list1(tag(" "), map(u64::<&str, ErrorTree<&str>>, |e| e as usize)),
+            input,
+        )
     }
 }
 #[cfg(test)]
@@ -80,7 +81,7 @@ mod tests {
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        assert_eq!(vec![125, 17], parser::parse(SAMPLE));
+        assert_eq!(vec![125, 17], parser::parse(SAMPLE)?);
         Ok(())
     }
 
@@ -0,0 +1,368 @@
+use std::fmt::Display;
+use thiserror::Error;
+use utils::math::{ext_gcd, gcd};
+
+pub const PART2_OFFSET: i128 = 10000000000000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Rational {
+    pub num: i128,
+    pub den: i128,
+}
+
+impl Rational {
+    pub fn new(num: i128, den: i128) -> Self {
+        let g = gcd(num, den).max(1) * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.num < 0
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Solution {
+    pub a: i128,
+    pub b: i128,
+    pub cost: i128,
+}
+
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum Unreachable {
+    #[error("needs a fractional number of presses (A={a}, B={b})")]
+    Fractional { a: Rational, b: Rational },
+    #[error("needs a negative number of presses (A={a}, B={b})")]
+    Negative { a: Rational, b: Rational },
+    #[error("needs more than {limit} presses (A={a}, B={b})")]
+    TooManyPresses { a: i128, b: i128, limit: i128 },
+    #[error("the buttons both move along one line and the prize is not on it")]
+    OffLine,
+    #[error("the buttons move in steps of {step} along their line, which never lands on {target}")]
+    Indivisible { step: i128, target: i128 },
+    #[error("no combination of presses within the limit lands on the prize")]
+    NoCombination,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Machine {
+    pub button_a: (i128, i128),
+    pub button_b: (i128, i128),
+    pub prize: (i128, i128),
+    pub cost_a: i128,
+    pub cost_b: i128,
+}
+
+impl Machine {
+    pub fn new(button_a: (i128, i128), button_b: (i128, i128), prize: (i128, i128)) -> Self {
+        Self {
+            button_a,
+            button_b,
+            prize,
+            cost_a: 3,
+            cost_b: 1,
+        }
+    }
+
+    pub fn cost(&self, a: i128, b: i128) -> i128 {
+        self.cost_a * a + self.cost_b * b
+    }
+
+    pub fn solve(&self, offset: i128, limit: Option<i128>) -> Result<Solution, Unreachable> {
+        let (ax, ay) = self.button_a;
+        let (bx, by) = self.button_b;
+        let prize = (self.prize.0 + offset, self.prize.1 + offset);
+        let det = ax * by - ay * bx;
+        if det == 0 {
+            return self.solve_collinear(prize, limit);
+        }
+
+        let a = Rational::new(prize.0 * by - prize.1 * bx, det);
+        let b = Rational::new(ax * prize.1 - ay * prize.0, det);
+        if !a.is_integer() || !b.is_integer() {
+            return Err(Unreachable::Fractional { a, b });
+        }
+        if a.is_negative() || b.is_negative() {
+            return Err(Unreachable::Negative { a, b });
+        }
+        let (a, b) = (a.num, b.num);
+        if let Some(limit) = limit.filter(|&l| a > l || b > l) {
+            return Err(Unreachable::TooManyPresses { a, b, limit });
+        }
+        Ok(Solution {
+            a,
+            b,
+            cost: self.cost(a, b),
+        })
+    }
+
+    fn solve_collinear(
+        &self,
+        prize: (i128, i128),
+        limit: Option<i128>,
+    ) -> Result<Solution, Unreachable> {
+        let cross = |v: (i128, i128)| v.0 * prize.1 - v.1 * prize.0;
+        let (u, v, target) = if self.button_a.0 != 0 || self.button_b.0 != 0 {
+            (self.button_a.0, self.button_b.0, prize.0)
+        } else if self.button_a.1 != 0 || self.button_b.1 != 0 {
+            (self.button_a.1, self.button_b.1, prize.1)
+        } else if prize == (0, 0) {
+            return Ok(Solution {
+                a: 0,
+                b: 0,
+                cost: 0,
+            });
+        } else {
+            return Err(Unreachable::OffLine);
+        };
+        if cross(self.button_a) != 0 || cross(self.button_b) != 0 {
+            return Err(Unreachable::OffLine);
+        }
+
+        let (step, x, y) = ext_gcd(u, v);
+        if target % step != 0 {
+            return Err(Unreachable::Indivisible { step, target });
+        }
+
+        // Every solution is (a0 + k.s, b0 - k.r) for integer k
+        let (a0, b0) = (x * (target / step), y * (target / step));
+        let (s, r) = (v / step, u / step);
+        let mut lo: Option<i128> = None;
+        let mut hi: Option<i128> = None;
+        let mut feasible = true;
+        let mut constrain = |c: i128, m: i128| match m.signum() {
+            1 => lo = Some(lo.unwrap_or(i128::MIN).max(-(c.div_euclid(m)))),
+            -1 => hi = Some(hi.unwrap_or(i128::MAX).min(c.div_euclid(-m))),
+            _ => feasible &= c >= 0,
+        };
+        constrain(a0, s);
+        constrain(b0, -r);
+        if let Some(limit) = limit {
+            constrain(limit - a0, -s);
+            constrain(limit - b0, r);
+        }
+        if !feasible || matches!((lo, hi), (Some(lo), Some(hi)) if lo > hi) {
+            return Err(Unreachable::NoCombination);
+        }
+
+        let slope = self.cost_a * s - self.cost_b * r;
+        let k = if slope > 0 { lo.or(hi) } else { hi.or(lo) }.unwrap_or(0);
+        let (a, b) = (a0 + k * s, b0 - k * r);
+        Ok(Solution {
+            a,
+            b,
+            cost: self.cost(a, b),
+        })
+    }
+}
+
+pub mod parser {
+    use nom::{character::complete::line_ending, sequence::terminated, IResult};
+
+    use super::*;
+    use nom_supreme::error::ErrorTree;
+    use utils::error::ParseError;
+    use utils::parse::{blocks, parse_all, AocParse};
+
+    #[derive(AocParse)]
+    #[aoc("Button A: X+{x}, Y+{y}")]
+    struct ButtonA {
+        x: i128,
+        y: i128,
+    }
+
+    #[derive(AocParse)]
+    #[aoc("Button B: X+{x}, Y+{y}")]
+    struct ButtonB {
+        x: i128,
+        y: i128,
+    }
+
+    #[derive(AocParse)]
+    #[aoc("Prize: X={x}, Y={y}")]
+    struct Prize {
+        x: i128,
+        y: i128,
+    }
+
+    pub fn parse(input: &str) -> Result<Vec<Machine>, ParseError> {
+        parse_all(blocks(machine), input)
+    }
+
+    pub fn machine(input: &str) -> IResult<&str, Machine, ErrorTree<&str>> {
+        let (input, a) = terminated(ButtonA::parse_nom, line_ending)(input)?;
+        let (input, b) = terminated(ButtonB::parse_nom, line_ending)(input)?;
+        let (input, prize) = Prize::parse_nom(input)?;
+        Ok((
+            input,
+            Machine::new((a.x, a.y), (b.x, b.y), (prize.x, prize.y)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn test_parse() {
+        let machines = parser::parse(SAMPLE).unwrap();
+        assert_eq!(4, machines.len());
+        assert_eq!(22, machines[0].button_b.0);
+        assert_eq!(66, machines[1].button_a.1);
+        assert_eq!(7870, machines[2].prize.0);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "Button A: X+94, Y+34\nButton B: X+22, Y-67\nPrize: X=8400, Y=5400";
+        let err = parser::parse(input).unwrap_err();
+        assert_eq!(2, err.line);
+        assert_eq!(15, err.column);
+        assert_eq!("\", Y+\"", err.expected);
+        assert_eq!("Button B: X+22, Y-67", err.snippet);
+    }
+
+    #[test]
+    fn test_rational() {
+        assert_eq!(Rational { num: 2, den: 3 }, Rational::new(-4, -6));
+        assert_eq!(Rational { num: -2, den: 3 }, Rational::new(4, -6));
+        assert_eq!("-2/3", Rational::new(4, -6).to_string());
+        assert_eq!("5", Rational::new(10, 2).to_string());
+        assert!(Rational::new(0, 7).is_integer());
+    }
+
+    #[test]
+    fn test_solve() {
+        let machines = parser::parse(SAMPLE).unwrap();
+        assert_eq!(
+            Ok(Solution {
+                a: 80,
+                b: 40,
+                cost: 280
+            }),
+            machines[0].solve(0, Some(100))
+        );
+        assert_eq!(
+            Err(Unreachable::Fractional {
+                a: Rational::new(548084, 3876),
+                b: Rational::new(-524792, -3876)
+            }),
+            machines[1].solve(0, Some(100))
+        );
+        assert_eq!(200, machines[2].solve(0, Some(100)).unwrap().cost);
+        assert!(machines[3].solve(0, Some(100)).is_err());
+        assert_eq!(
+            459236326669,
+            machines[1].solve(PART2_OFFSET, None).unwrap().cost
+        );
+        assert_eq!(
+            416082282239,
+            machines[3].solve(PART2_OFFSET, None).unwrap().cost
+        );
+    }
+
+    #[test]
+    fn test_unreachable_reasons() {
+        let machine = Machine::new((1, 0), (0, 1), (-2, 5));
+        assert_eq!(
+            "needs a negative number of presses (A=-2, B=5)",
+            machine.solve(0, None).unwrap_err().to_string()
+        );
+        let machine = Machine::new((1, 0), (0, 1), (200, 5));
+        assert_eq!(
+            Err(Unreachable::TooManyPresses {
+                a: 200,
+                b: 5,
+                limit: 100
+            }),
+            machine.solve(0, Some(100))
+        );
+    }
+
+    #[test]
+    fn test_collinear() {
+        let machine = Machine::new((1, 1), (3, 3), (6, 6));
+        assert_eq!(
+            Ok(Solution {
+                a: 0,
+                b: 2,
+                cost: 2
+            }),
+            machine.solve(0, None)
+        );
+
+        let machine = Machine {
+            cost_a: 1,
+            cost_b: 5,
+            ..machine
+        };
+        assert_eq!(
+            Ok(Solution {
+                a: 6,
+                b: 0,
+                cost: 6
+            }),
+            machine.solve(0, None)
+        );
+        assert_eq!(
+            Ok(Solution {
+                a: 3,
+                b: 1,
+                cost: 8
+            }),
+            machine.solve(0, Some(4))
+        );
+        assert_eq!(Err(Unreachable::NoCombination), machine.solve(0, Some(1)));
+
+        let machine = Machine::new((2, 2), (3, 3), (7, 7));
+        assert_eq!(
+            Some((2, 1)),
+            machine.solve(0, None).ok().map(|e| (e.a, e.b))
+        );
+
+        let machine = Machine::new((1, 1), (2, 2), (3, 4));
+        assert_eq!(Err(Unreachable::OffLine), machine.solve(0, None));
+
+        let machine = Machine::new((2, 2), (4, 4), (3, 3));
+        assert_eq!(
+            Err(Unreachable::Indivisible { step: 2, target: 3 }),
+            machine.solve(0, None)
+        );
+
+        let machine = Machine::new((0, 2), (0, 4), (0, 6));
+        assert_eq!(Ok(4), machine.solve(0, None).map(|e| e.cost));
+    }
+}
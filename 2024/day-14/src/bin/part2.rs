@@ -1,5 +1,6 @@
+use day_14::common::{parser, Detector, LowEntropy, LowVariance, RowRun, Zone};
 use day_14::part2::process;
-use miette::Context;
+use miette::{Context, IntoDiagnostic};
 use tracing_subscriber::fmt::format::FmtSpan;
 
 
@@ -15,5 +16,21 @@ fn main() -> miette::Result<()> {
     let file = include_str!("../../input2.txt");
     let result = process(file).context("process part 2")?;
     println!("{}", result);
+
+    // Pass a directory to dump the candidate frames from each detector.
+    if let Some(dir) = std::env::args().nth(1) {
+        let zone = Zone::new(101, 103, parser::parse(file)?);
+        let detectors: [&dyn Detector; 3] =
+            [&RowRun::new(8), &LowVariance, &LowEntropy::new(4, 5)];
+        let mut times = detectors
+            .iter()
+            .flat_map(|e| e.candidates(&zone))
+            .collect::<Vec<_>>();
+        times.sort();
+        times.dedup();
+        zone.dump(std::path::Path::new(&dir), &times)
+            .into_diagnostic()
+            .context("dump frames")?;
+    }
     Ok(())
 }
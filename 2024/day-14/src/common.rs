@@ -1,6 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Debug, Display};
+use std::path::Path;
 use utils::grid::Xy;
+use utils::math::{crt, gcd};
+use utils::parse::AocParse;
 
 pub struct Zone {
     pub width: i32,
@@ -46,7 +49,6 @@ impl Zone {
     pub fn count(&self) -> [i32; 4] {
         let lim_x = (self.width - 1) / 2;
         let lim_y = (self.height - 1) / 2;
-        dbg!(lim_x, lim_y);
         let q1 = self
             .robots
             .iter()
@@ -70,35 +72,230 @@ impl Zone {
         [q1, q2, q3, q4]
     }
 
-    pub fn step(&mut self) {
-        self.count += 1;
-        for i in 0..self.robots.len() {
-            self.step_robot(i);
+    pub fn period(&self) -> i32 {
+        self.width / gcd(self.width as i64, self.height as i64) as i32 * self.height
+    }
+
+    pub fn positions(&self, t: i32) -> Vec<Xy> {
+        self.robots
+            .iter()
+            .map(|e| e.at(t, self.width, self.height))
+            .collect()
+    }
+
+    pub fn advance(&mut self, t: i32) {
+        self.count += t;
+        for robot in self.robots.iter_mut() {
+            robot.pos = robot.at(t, self.width, self.height);
         }
     }
 
+    pub fn step(&mut self) {
+        self.advance(1);
+    }
+
     pub fn step_robot(&mut self, index: usize) {
         let robot = &mut self.robots[index];
-        robot.pos.x += robot.vel.x;
-        if robot.pos.x < 0 {
-            robot.pos.x += self.width
-        };
-        if robot.pos.x >= self.width {
-            robot.pos.x -= self.width
-        };
-        robot.pos.y += robot.vel.y;
-        if robot.pos.y < 0 {
-            robot.pos.y += self.height
-        };
-        if robot.pos.y >= self.height {
-            robot.pos.y -= self.height
-        };
+        robot.pos = robot.at(1, self.width, self.height);
+    }
+
+    pub fn pbm(&self, t: i32) -> String {
+        let occupied = self.positions(t).into_iter().collect::<HashSet<_>>();
+        let mut image = format!(
+            "P1\n# t={}\n{} {}\n",
+            self.count + t,
+            self.width,
+            self.height
+        );
+        for y in 0..self.height {
+            let row = (0..self.width)
+                .map(|x| {
+                    if occupied.contains(&Xy::new(x, y)) {
+                        "1"
+                    } else {
+                        "0"
+                    }
+                })
+                .collect::<Vec<_>>();
+            image.push_str(&row.join(" "));
+            image.push('\n');
+        }
+        image
+    }
+
+    /// Shades each cell by the number of robots on it.
+    pub fn ppm(&self, t: i32) -> String {
+        let mut counts: HashMap<Xy, usize> = HashMap::new();
+        for pos in self.positions(t) {
+            *counts.entry(pos).or_default() += 1;
+        }
+        let max = counts.values().copied().max().unwrap_or(1);
+        let mut image = format!(
+            "P3\n# t={}\n{} {}\n255\n",
+            self.count + t,
+            self.width,
+            self.height
+        );
+        for y in 0..self.height {
+            let row = (0..self.width)
+                .map(|x| match counts.get(&Xy::new(x, y)) {
+                    Some(&n) => format!("0 {} 0", 64 + 191 * n / max),
+                    None => "0 0 0".to_string(),
+                })
+                .collect::<Vec<_>>();
+            image.push_str(&row.join(" "));
+            image.push('\n');
+        }
+        image
+    }
+
+    pub fn dump(&self, dir: &Path, times: &[i32]) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        for &t in times {
+            let name = format!("frame-{:05}", self.count + t);
+            std::fs::write(dir.join(format!("{}.pbm", name)), self.pbm(t))?;
+            std::fs::write(dir.join(format!("{}.ppm", name)), self.ppm(t))?;
+        }
+        Ok(())
+    }
+}
+
+/// Offsets within one period that might show the tree, best first.
+pub trait Detector {
+    fn candidates(&self, zone: &Zone) -> Vec<i32>;
+}
+
+pub struct RowRun {
+    pub length: usize,
+}
+
+impl RowRun {
+    pub fn new(length: usize) -> Self {
+        Self { length }
+    }
+
+    pub fn matches(&self, positions: &[Xy]) -> bool {
+        let mut positions = positions.to_vec();
+        positions.sort_by_key(|e| (e.y, e.x));
+        positions.dedup();
+        let mut run = 1;
+        for pair in positions.windows(2) {
+            if pair[1].y == pair[0].y && pair[1].x - pair[0].x == 1 {
+                run += 1;
+            } else {
+                run = 1;
+            }
+            if run >= self.length {
+                return true;
+            }
+        }
+        self.length <= 1 && !positions.is_empty()
+    }
+}
+
+impl Detector for RowRun {
+    fn candidates(&self, zone: &Zone) -> Vec<i32> {
+        (0..zone.period())
+            .filter(|&t| self.matches(&zone.positions(t)))
+            .collect()
+    }
+}
+
+/// Clusters each axis separately and combines the two with the CRT.
+pub struct LowVariance;
+
+impl LowVariance {
+    fn tightest(zone: &Zone, period: i32, axis: impl Fn(&Robot) -> (i32, i32)) -> i32 {
+        (0..period)
+            .min_by_key(|&t| {
+                let values = zone
+                    .robots
+                    .iter()
+                    .map(|e| {
+                        let (pos, vel) = axis(e);
+                        (pos + vel * t).rem_euclid(period) as i64
+                    })
+                    .collect::<Vec<_>>();
+                let n = values.len() as i64;
+                let sum = values.iter().sum::<i64>();
+                let squares = values.iter().map(|e| e * e).sum::<i64>();
+                n * squares - sum * sum
+            })
+            .unwrap_or(0)
+    }
+}
+
+impl Detector for LowVariance {
+    fn candidates(&self, zone: &Zone) -> Vec<i32> {
+        let tx = Self::tightest(zone, zone.width, |e| (e.pos.x, e.vel.x));
+        let ty = Self::tightest(zone, zone.height, |e| (e.pos.y, e.vel.y));
+        crt(tx as i64, zone.width as i64, ty as i64, zone.height as i64)
+            .map(|(t, _)| t as i32)
+            .into_iter()
+            .collect()
+    }
+}
+
+pub struct LowEntropy {
+    pub cell: i32,
+    pub limit: usize,
+}
+
+impl LowEntropy {
+    pub fn new(cell: i32, limit: usize) -> Self {
+        Self { cell, limit }
+    }
+
+    pub fn entropy(&self, positions: &[Xy]) -> f64 {
+        let mut counts: HashMap<Xy, usize> = HashMap::new();
+        for pos in positions {
+            *counts
+                .entry(Xy::new(pos.x / self.cell, pos.y / self.cell))
+                .or_default() += 1;
+        }
+        let total = positions.len() as f64;
+        counts
+            .values()
+            .map(|&e| {
+                let p = e as f64 / total;
+                -p * p.ln()
+            })
+            .sum()
+    }
+}
+
+impl Detector for LowEntropy {
+    fn candidates(&self, zone: &Zone) -> Vec<i32> {
+        let mut scored = (0..zone.period())
+            .map(|t| (self.entropy(&zone.positions(t)), t))
+            .collect::<Vec<_>>();
+        scored.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        scored
+            .into_iter()
+            .take(self.limit)
+            .map(|(_, t)| t)
+            .collect()
     }
 }
+
+#[derive(AocParse)]
+#[aoc("p={pos} v={vel}")]
 pub struct Robot {
     pub pos: Xy,
     pub vel: Xy,
 }
+impl Robot {
+    pub fn at(&self, t: i32, width: i32, height: i32) -> Xy {
+        let wrap = |pos: i32, vel: i32, size: i32| {
+            (pos as i64 + vel as i64 * t.rem_euclid(size) as i64).rem_euclid(size as i64) as i32
+        };
+        Xy::new(
+            wrap(self.pos.x, self.vel.x, width),
+            wrap(self.pos.y, self.vel.y, height),
+        )
+    }
+}
+
 impl Debug for Robot {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "R({},{})", self.pos.x, self.pos.y)
@@ -107,29 +304,11 @@ impl Debug for Robot {
 
 pub mod parser {
     use super::*;
-    use nom::{
-        bytes::complete::tag,
-        character::complete::{i32, line_ending},
-        multi::separated_list1,
-        sequence::{preceded, separated_pair},
-        IResult,
-    };
-
-    pub fn parse(input: &str) -> Vec<Robot> {
-        let (_, robots) = separated_list1(line_ending, robot)(input).unwrap();
-        robots
-    }
-
-    fn robot(input: &str) -> IResult<&str, Robot> {
-        let (input, (p1, p2)) = preceded(tag("p="), separated_pair(i32, tag(","), i32))(input)?;
-        let (input, (v1, v2)) = preceded(tag(" v="), separated_pair(i32, tag(","), i32))(input)?;
-        Ok((
-            input,
-            Robot {
-                pos: (p1, p2).into(),
-                vel: (v1, v2).into(),
-            },
-        ))
+    use utils::error::ParseError;
+    use utils::parse::{lines, parse_all};
+
+    pub fn parse(input: &str) -> Result<Vec<Robot>, ParseError> {
+        parse_all(lines(Robot::parse_nom), input)
     }
 }
 #[cfg(test)]
@@ -151,7 +330,7 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        let robots = parser::parse(SAMPLE);
+        let robots = parser::parse(SAMPLE).unwrap();
         assert_eq!(12, robots.len());
         assert_eq!(Xy::new(6, 3), robots[1].pos);
         assert_eq!(Xy::new(2, -1), robots[3].vel);
@@ -160,7 +339,7 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_step() -> miette::Result<()> {
-        let robots = parser::parse(SAMPLE);
+        let robots = parser::parse(SAMPLE).unwrap();
         let mut zone = Zone::new(11, 7, robots);
         assert_eq!(Xy::new(2, 4), zone.robots[10].pos);
         zone.step_robot(10);
@@ -176,7 +355,7 @@ p=9,5 v=-3,-3";
 
     #[test]
     fn test_count() -> miette::Result<()> {
-        let robots = parser::parse(SAMPLE);
+        let robots = parser::parse(SAMPLE).unwrap();
         let mut zone = Zone::new(11, 7, robots);
         for _ in 0..100 {
             zone.step();
@@ -184,4 +363,73 @@ p=9,5 v=-3,-3";
         assert_eq!(12, zone.safety());
         Ok(())
     }
+
+    #[test]
+    fn test_advance() -> miette::Result<()> {
+        let robots = parser::parse(SAMPLE).unwrap();
+        let mut stepped = Zone::new(11, 7, robots);
+        let robots = parser::parse(SAMPLE).unwrap();
+        let mut jumped = Zone::new(11, 7, robots);
+        assert_eq!(77, jumped.period());
+        assert_eq!(Xy::new(10, 6), jumped.positions(4)[10]);
+        for _ in 0..100 {
+            stepped.step();
+        }
+        jumped.advance(100);
+        assert_eq!(stepped.positions(0), jumped.positions(0));
+        assert_eq!(100, jumped.count);
+        assert_eq!(jumped.positions(0), jumped.positions(jumped.period()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_time() {
+        let robot = Robot {
+            pos: Xy::new(2, 4),
+            vel: Xy::new(2, -3),
+        };
+        assert_eq!(robot.at(i32::MAX % 77, 11, 7), robot.at(i32::MAX, 11, 7));
+        assert_eq!(
+            robot.at(77 - i32::MAX % 77, 11, 7),
+            robot.at(-i32::MAX, 11, 7)
+        );
+        let robot = Robot {
+            pos: Xy::new(100, 102),
+            vel: Xy::new(-100, 100),
+        };
+        assert_eq!(Xy::new(10, 38), robot.at(1_000_000_001, 101, 103));
+    }
+
+    #[test]
+    fn test_row_run() {
+        let positions = (0..8).map(|x| Xy::new(x, 0)).collect::<Vec<_>>();
+        assert!(RowRun::new(8).matches(&positions));
+        assert!(!RowRun::new(9).matches(&positions));
+        let mut positions = positions;
+        positions[2] = Xy::new(3, 0);
+        assert!(!RowRun::new(8).matches(&positions));
+        positions[2] = Xy::new(2, 1);
+        assert!(!RowRun::new(8).matches(&positions));
+        assert!(RowRun::new(5).matches(&positions));
+    }
+
+    #[test]
+    fn test_entropy() {
+        let detector = LowEntropy::new(2, 1);
+        let clustered = vec![Xy::new(0, 0), Xy::new(1, 1), Xy::new(0, 1), Xy::new(1, 0)];
+        let spread = vec![Xy::new(0, 0), Xy::new(3, 3), Xy::new(6, 0), Xy::new(9, 3)];
+        assert_eq!(0.0, detector.entropy(&clustered));
+        assert!(detector.entropy(&spread) > detector.entropy(&clustered));
+    }
+
+    #[test]
+    fn test_images() {
+        let robots = parser::parse("p=0,0 v=1,0\np=1,0 v=1,0\np=1,1 v=0,0").unwrap();
+        let zone = Zone::new(3, 2, robots);
+        assert_eq!("P1\n# t=1\n3 2\n0 1 1\n0 1 0\n", zone.pbm(1));
+        assert_eq!(
+            "P3\n# t=0\n3 2\n255\n0 255 0 0 255 0 0 0 0\n0 0 0 0 255 0 0 0 0\n",
+            zone.ppm(0)
+        );
+    }
 }
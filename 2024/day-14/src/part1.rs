@@ -3,11 +3,9 @@ use crate::custom_error::AocError;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let robots = parser::parse(input);
+    let robots = parser::parse(input)?;
     let mut zone = Zone::new(101, 103, robots);
-    for _i in 0..100 {
-        zone.step();
-    }
+    zone.advance(100);
     Ok(zone.safety().to_string())
 }
 
@@ -1,3 +1,4 @@
+use std::collections::{HashSet, VecDeque};
 use std::fmt::Display;
 use std::marker::PhantomData;
 use utils::grid::*;
@@ -11,9 +12,7 @@ pub trait WarehouseType {
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Item {
     Wall,
-    Box,
-    BoxLeft,
-    BoxRight,
+    Box(usize),
     Robot,
     Empty,
 }
@@ -22,23 +21,49 @@ impl Display for Item {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Wall => write!(f, "#"),
-            Self::Box => write!(f, "O"),
-            Self::BoxLeft => write!(f, "["),
-            Self::BoxRight => write!(f, "]"),
+            Self::Box(_) => write!(f, "O"),
             Self::Robot => write!(f, "@"),
             Self::Empty => write!(f, "."),
         }
     }
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Object {
+    pub cells: Vec<(Xy, char)>,
+}
+
+impl Object {
+    pub fn gps(&self) -> i32 {
+        let x = self.cells.iter().map(|(e, _)| e.x).min().unwrap_or(0);
+        let y = self.cells.iter().map(|(e, _)| e.y).min().unwrap_or(0);
+        x + (100 * y)
+    }
+
+    pub fn glyph(&self, xy: Xy) -> Option<char> {
+        self.cells.iter().find(|(e, _)| *e == xy).map(|(_, c)| *c)
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Step {
+    pub index: usize,
+    pub instruction: Direction4,
+    pub robot: Xy,
+    pub pushed: Option<Vec<usize>>,
+    pub map: String,
+}
+
 pub struct Warehouse<T>
 where
     T: WarehouseType,
 {
     pub grid: Grid<Item>,
+    pub objects: Vec<Object>,
     pub robot_location: Xy,
     pub instructions: Vec<Direction4>,
     pub instruction_index: usize,
+    pub log: Option<Vec<Step>>,
     marker: PhantomData<T>,
 }
 
@@ -46,94 +71,135 @@ impl<T> Warehouse<T>
 where
     T: WarehouseType,
 {
+    pub fn new() -> Self {
+        Self {
+            grid: Grid::empty(),
+            objects: vec![],
+            robot_location: Xy::new(0, 0),
+            instructions: vec![],
+            instruction_index: 0,
+            log: None,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn add_wall(&mut self, xy: Xy) {
+        self.grid.insert(xy, Item::Wall);
+    }
+
+    pub fn add_empty(&mut self, xy: Xy) {
+        self.grid.insert(xy, Item::Empty);
+    }
+
+    pub fn add_object(&mut self, cells: Vec<(Xy, char)>) -> usize {
+        let id = self.objects.len();
+        for (xy, _) in cells.iter() {
+            self.grid.insert(*xy, Item::Box(id));
+        }
+        self.objects.push(Object { cells });
+        id
+    }
+
+    pub fn record(&mut self) {
+        self.log.get_or_insert_with(Vec::new);
+    }
+
     pub fn score(&self) -> i32 {
-        self.grid
-            .all()
-            .filter(|(_k, v)| *v == Some(&Item::BoxLeft) || *v == Some(&Item::Box))
-            .map(|(k, _v)| k.x + (100 * k.y))
-            .sum()
+        self.objects.iter().map(|e| e.gps()).sum()
     }
+
     pub fn done(&self) -> bool {
         self.instruction_index >= self.instructions.len()
     }
+
     pub fn step(&mut self) {
         let instruction = self.instructions[self.instruction_index];
         self.instruction_index += 1;
-        self.step_up_down(instruction);
-    }
-
-    pub fn can_move(&self, xy: Xy, direction: Direction4) -> bool {
-        let is_up_down = direction == Direction4::S || direction == Direction4::N;
-        match self.grid.get(xy) {
-            None => panic!("Out of bounds"),
-            Some(&Item::Robot) => panic!("Robot invasion"),
-            Some(&Item::Wall) => false,
-            Some(&Item::Empty) => true,
-            Some(&Item::Box) => self.can_move(xy + direction, direction),
-            Some(&Item::BoxLeft) if is_up_down => {
-                self.can_move(xy + direction, direction)
-                    && self.can_move(xy + direction + Direction4::E, direction)
-            }
-            Some(&Item::BoxLeft) => self.can_move(xy + direction, direction),
-            Some(&Item::BoxRight) if is_up_down => {
-                self.can_move(xy + direction, direction)
-                    && self.can_move(xy + Direction4::W + direction, direction)
-            }
-            Some(&Item::BoxRight) => self.can_move(xy + direction, direction),
+        let pushed = self.push(instruction);
+        if self.log.is_none() {
+            return;
+        }
+        let (index, robot, map) = (
+            self.instruction_index - 1,
+            self.robot_location,
+            self.render_map(),
+        );
+        if let Some(log) = self.log.as_mut() {
+            log.push(Step {
+                index,
+                instruction,
+                robot,
+                pushed,
+                map,
+            });
         }
     }
 
-    pub fn do_move(&mut self, xy: Xy, direction: Direction4) {
-        let is_up_down = direction == Direction4::S || direction == Direction4::N;
-        match self.grid.get(xy) {
-            None => panic!("Out of bounds"),
-            Some(&Item::Robot) => panic!("Robot invasion"),
-            Some(&Item::Wall) => {}
-            Some(&Item::Empty) => {}
-            Some(&Item::BoxLeft) if is_up_down => {
-                self.do_move(xy + direction, direction);
-                self.do_move(xy + direction + Direction4::E, direction);
-                self.grid.insert(xy + direction, Item::BoxLeft);
-                self.grid.insert(xy, Item::Empty);
-                self.grid
-                    .insert(xy + direction + Direction4::E, Item::BoxRight);
-                self.grid.insert(xy + Direction4::E, Item::Empty);
-            }
-            Some(&Item::BoxLeft) => {
-                self.do_move(xy + direction, direction);
-                self.grid.insert(xy + direction, Item::BoxLeft);
-                self.grid.insert(xy, Item::Empty);
-            }
-            Some(&Item::BoxRight) if is_up_down => {
-                self.do_move(xy + Direction4::W, direction);
+    /// `None` if the chain of objects runs into a wall.
+    pub fn affected(&self, direction: Direction4) -> Option<Vec<usize>> {
+        let mut affected = vec![];
+        let mut seen = HashSet::new();
+        let mut queue = VecDeque::from([self.robot_location + direction]);
+        while let Some(xy) = queue.pop_front() {
+            match self.grid.get(xy) {
+                None => panic!("Out of bounds"),
+                Some(&Item::Robot) => panic!("Robot invasion"),
+                Some(&Item::Wall) => return None,
+                Some(&Item::Empty) => {}
+                Some(&Item::Box(id)) => {
+                    if seen.insert(id) {
+                        affected.push(id);
+                        queue.extend(self.objects[id].cells.iter().map(|(e, _)| *e + direction));
+                    }
+                }
             }
-            Some(&Item::BoxRight) => {
-                self.do_move(xy + direction, direction);
-                self.grid.insert(xy + direction, Item::BoxRight);
-                self.grid.insert(xy, Item::Empty);
+        }
+        Some(affected)
+    }
+
+    pub fn push(&mut self, direction: Direction4) -> Option<Vec<usize>> {
+        let affected = self.affected(direction)?;
+        for &id in affected.iter() {
+            for (xy, _) in self.objects[id].cells.iter() {
+                self.grid.insert(*xy, Item::Empty);
             }
-            Some(&Item::Box) => {
-                self.do_move(xy + direction, direction);
-                self.grid.insert(xy + direction, Item::Box);
-                self.grid.insert(xy, Item::Empty);
+        }
+        for &id in affected.iter() {
+            for (xy, _) in self.objects[id].cells.iter_mut() {
+                *xy = *xy + direction;
+                self.grid.insert(*xy, Item::Box(id));
             }
         }
+        self.robot_location = self.robot_location + direction;
+        Some(affected)
     }
-    pub fn step_up_down(&mut self, instruction: Direction4) {
-        let is_up_down = instruction == Direction4::N || instruction == Direction4::S;
-        if !self.can_move(self.robot_location + instruction, instruction) {
-            return;
-        }
-        if is_up_down && Some(&Item::BoxRight) == self.grid.get(self.robot_location + instruction) {
-            self.do_move(
-                self.robot_location + instruction + Direction4::W,
-                instruction,
-            );
-        } else {
-            self.do_move(self.robot_location + instruction, instruction);
+
+    pub fn render_map(&self) -> String {
+        let mut map = String::new();
+        for y in 0..self.grid.height() {
+            map.push_str(&format!("{:<04} ", y));
+            for x in 0..self.grid.width() {
+                let xy = Xy::new(x, y);
+                let c = match self.grid.get(xy) {
+                    _ if xy == self.robot_location => '@',
+                    Some(&Item::Box(id)) => self.objects[id].glyph(xy).unwrap_or('O'),
+                    Some(item) => item.to_string().chars().next().unwrap_or('.'),
+                    None => '.',
+                };
+                map.push(c);
+            }
+            map.push('\n');
         }
-        self.grid.insert(self.robot_location, Item::Empty);
-        self.robot_location = self.robot_location + instruction;
+        map
+    }
+}
+
+impl<T> Default for Warehouse<T>
+where
+    T: WarehouseType,
+{
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -142,21 +208,7 @@ where
     T: WarehouseType,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for y in 0..self.grid.height() {
-            write!(f, "{:<04} ", y)?;
-            for x in 0..self.grid.width() {
-                if Xy::new(x, y) == self.robot_location {
-                    write!(f, "{}", Item::Robot)?;
-                } else {
-                    write!(
-                        f,
-                        "{}",
-                        self.grid.get(Xy::new(x, y)).unwrap_or(&Item::Empty)
-                    )?;
-                }
-            }
-            writeln!(f)?;
-        }
+        write!(f, "{}", self.render_map())?;
         let mut index = 0;
         for each in self.instructions.chunks(80) {
             for d in each.iter() {
@@ -179,13 +231,7 @@ pub mod parser {
     where
         T: WarehouseType,
     {
-        let mut warehouse = Warehouse::<T> {
-            grid: Grid::empty(),
-            robot_location: Xy::new(0, 0),
-            instructions: vec![],
-            instruction_index: 0,
-            marker: PhantomData,
-        };
+        let mut warehouse = Warehouse::<T>::new();
         let lines = input.lines().enumerate().collect::<Vec<_>>();
         let mut index = 0;
         loop {
@@ -217,3 +263,107 @@ pub mod parser {
         warehouse
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Triple;
+
+    impl WarehouseType for Triple {
+        fn add_item<T: WarehouseType>(x: i32, y: i32, c: char, warehouse: &mut Warehouse<T>) {
+            match c {
+                '#' => warehouse.add_wall((x, y).into()),
+                'O' => {
+                    warehouse.add_object((0..3).map(|i| (Xy::new(x + i, y), 'O')).collect());
+                    return;
+                }
+                'L' => {
+                    warehouse.add_object(vec![
+                        (Xy::new(x, y), 'L'),
+                        (Xy::new(x, y + 1), 'L'),
+                        (Xy::new(x + 1, y + 1), 'L'),
+                    ]);
+                    return;
+                }
+                '@' => {
+                    warehouse.robot_location = Xy::new(x, y);
+                }
+                _ => {}
+            }
+            if warehouse.grid.get((x, y).into()).is_none() {
+                warehouse.add_empty((x, y).into());
+            }
+        }
+    }
+
+    const SAMPLE: &str = "########
+#......#
+#.O....#
+#..O...#
+#...@..#
+########
+
+^^<";
+
+    #[test]
+    fn test_wide_push() {
+        let mut warehouse: Warehouse<Triple> = parser::parse(SAMPLE);
+        assert_eq!(2, warehouse.objects.len());
+        assert_eq!(Some(vec![1, 0]), warehouse.affected(Direction4::N));
+        warehouse.record();
+        warehouse.step();
+        assert_eq!(Xy::new(4, 3), warehouse.robot_location);
+        assert_eq!(Xy::new(3, 2), warehouse.objects[1].cells[0].0);
+        assert_eq!(Xy::new(2, 1), warehouse.objects[0].cells[0].0);
+        warehouse.step();
+        assert_eq!(Xy::new(4, 3), warehouse.robot_location);
+        warehouse.step();
+        assert_eq!(Xy::new(3, 3), warehouse.robot_location);
+
+        let log = warehouse.log.unwrap();
+        assert_eq!(3, log.len());
+        assert_eq!(Some(vec![1, 0]), log[0].pushed);
+        assert_eq!(None, log[1].pushed);
+        assert_eq!(Some(vec![]), log[2].pushed);
+        assert_eq!("0001 #.OOO..#", log[0].map.lines().nth(1).unwrap());
+        assert_eq!("0003 #..@...#", log[2].map.lines().nth(3).unwrap());
+        assert_eq!(
+            vec![0, 1, 2],
+            log.iter().map(|e| e.index).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_record_mid_run() {
+        let mut warehouse: Warehouse<Triple> = parser::parse(SAMPLE);
+        warehouse.step();
+        warehouse.record();
+        warehouse.step();
+        warehouse.step();
+        let log = warehouse.log.unwrap();
+        assert_eq!(vec![1, 2], log.iter().map(|e| e.index).collect::<Vec<_>>());
+        assert_eq!(None, log[0].pushed);
+    }
+
+    #[test]
+    fn test_shaped_push() {
+        let mut warehouse: Warehouse<Triple> = parser::parse(
+            "######
+#....#
+#L...#
+#....#
+#.@..#
+######
+
+^^",
+        );
+        assert_eq!(Some(vec![0]), warehouse.push(Direction4::N));
+        assert_eq!(None, warehouse.affected(Direction4::N));
+        assert_eq!(101, warehouse.objects[0].gps());
+        assert_eq!(
+            "0000 ######\n0001 #L...#\n0002 #LL..#\n0003 #.@..#\n0004 #....#\n0005 ######\n",
+            warehouse.render_map()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::common::{parser, Item, Warehouse, WarehouseType};
+use crate::common::{parser, Warehouse, WarehouseType};
 use crate::custom_error::AocError;
 use utils::grid::Xy;
 
@@ -18,16 +18,16 @@ impl WarehouseType for Standard {
     fn add_item<T: WarehouseType>(x: i32, y: i32, c: char, warehouse: &mut Warehouse<T>) {
         match c {
             '#' => {
-                warehouse.grid.insert((x, y).into(), Item::Wall);
+                warehouse.add_wall((x, y).into());
             }
             'O' => {
-                warehouse.grid.insert((x, y).into(), Item::Box);
+                warehouse.add_object(vec![((x, y).into(), 'O')]);
             }
             '.' => {
-                warehouse.grid.insert((x, y).into(), Item::Empty);
+                warehouse.add_empty((x, y).into());
             }
             '@' => {
-                warehouse.grid.insert((x, y).into(), Item::Empty);
+                warehouse.add_empty((x, y).into());
                 warehouse.robot_location = Xy::new(x, y);
             }
             _ => panic!("Bad map"),
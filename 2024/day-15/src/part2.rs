@@ -1,4 +1,4 @@
-use crate::common::{parser, Item, Warehouse, WarehouseType};
+use crate::common::{parser, Warehouse, WarehouseType};
 use crate::custom_error::AocError;
 use utils::grid::Xy;
 
@@ -17,22 +17,22 @@ impl WarehouseType for Wide {
     fn add_item<T: WarehouseType>(x: i32, y: i32, c: char, warehouse: &mut Warehouse<T>) {
         match c {
             '#' => {
-                warehouse.grid.insert((x * 2, y).into(), Item::Wall);
-                warehouse.grid.insert(((x * 2) + 1, y).into(), Item::Wall);
+                warehouse.add_wall((x * 2, y).into());
+                warehouse.add_wall(((x * 2) + 1, y).into());
             }
             'O' => {
-                warehouse.grid.insert((x * 2, y).into(), Item::BoxLeft);
-                warehouse
-                    .grid
-                    .insert(((x * 2) + 1, y).into(), Item::BoxRight);
+                warehouse.add_object(vec![
+                    ((x * 2, y).into(), '['),
+                    (((x * 2) + 1, y).into(), ']'),
+                ]);
             }
             '.' => {
-                warehouse.grid.insert((x * 2, y).into(), Item::Empty);
-                warehouse.grid.insert(((x * 2) + 1, y).into(), Item::Empty);
+                warehouse.add_empty((x * 2, y).into());
+                warehouse.add_empty(((x * 2) + 1, y).into());
             }
             '@' => {
-                warehouse.grid.insert((x * 2, y).into(), Item::Empty);
-                warehouse.grid.insert(((x * 2) + 1, y).into(), Item::Empty);
+                warehouse.add_empty((x * 2, y).into());
+                warehouse.add_empty(((x * 2) + 1, y).into());
                 warehouse.robot_location = Xy::new(x * 2, y);
             }
             _ => panic!("Bad map"),
@@ -0,0 +1,218 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt::Display;
+
+use utils::grid::{Direction4, Grid, Xy};
+
+pub const STEP_COST: i32 = 1;
+pub const TURN_COST: i32 = 1000;
+
+type State = (Xy, Direction4);
+
+pub struct Maze {
+    pub grid: Grid<char>,
+    pub start: Xy,
+    pub end: Xy,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub score: i32,
+    pub tiles: HashSet<Xy>,
+}
+
+impl Maze {
+    pub fn is_wall(&self, xy: Xy) -> bool {
+        !matches!(self.grid.get(xy), Some(c) if *c != '#')
+    }
+
+    fn neighbours(&self, (pos, dir): State, reverse: bool) -> Vec<(State, i32)> {
+        let next = if reverse { pos - dir } else { pos + dir };
+        let mut result = vec![
+            ((pos, dir.clockwise()), TURN_COST),
+            ((pos, dir.anticlockwise()), TURN_COST),
+        ];
+        if !self.is_wall(next) {
+            result.push(((next, dir), STEP_COST));
+        }
+        result
+    }
+
+    fn distances(&self, starts: &[State], reverse: bool) -> HashMap<State, i32> {
+        let mut dist = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for &state in starts {
+            dist.insert(state, 0);
+            queue.push(Reverse((0, state)));
+        }
+        while let Some(Reverse((score, state))) = queue.pop() {
+            if dist.get(&state).is_some_and(|&best| best < score) {
+                continue;
+            }
+            for (next, cost) in self.neighbours(state, reverse) {
+                let score = score + cost;
+                if dist.get(&next).is_none_or(|&best| score < best) {
+                    dist.insert(next, score);
+                    queue.push(Reverse((score, next)));
+                }
+            }
+        }
+        dist
+    }
+
+    pub fn solve(&self) -> Option<Solution> {
+        let from_start = self.distances(&[(self.start, Direction4::E)], false);
+        let score = Direction4::all()
+            .into_iter()
+            .filter_map(|d| from_start.get(&(self.end, d)))
+            .min()
+            .copied()?;
+
+        let ends = Direction4::all()
+            .into_iter()
+            .map(|d| (self.end, d))
+            .filter(|e| from_start.get(e) == Some(&score))
+            .collect::<Vec<_>>();
+        let to_end = self.distances(&ends, true);
+
+        let tiles = from_start
+            .iter()
+            .filter(|(state, s)| to_end.get(state).is_some_and(|&e| *s + e == score))
+            .map(|((pos, _), _)| *pos)
+            .collect();
+        Some(Solution { score, tiles })
+    }
+
+    pub fn render(&self, tiles: &HashSet<Xy>) -> String {
+        let mut result = String::new();
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                let xy = Xy::new(x, y);
+                if tiles.contains(&xy) {
+                    result.push('O');
+                } else {
+                    result.push(*self.grid.get(xy).unwrap_or(&' '));
+                }
+            }
+            result.push('\n');
+        }
+        result
+    }
+}
+
+impl Display for Maze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(&HashSet::new()))
+    }
+}
+
+pub mod parser {
+    use super::*;
+
+    pub fn parse(input: &str) -> Maze {
+        let mut grid = Grid::empty();
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                grid.insert(Xy::new(x as i32, y as i32), c);
+            }
+        }
+        let find = |target: char| {
+            grid.all()
+                .find(|(_k, v)| v == &Some(&target))
+                .map(|(k, _v)| k)
+        };
+        let start = find('S').expect("Didn't find the start position");
+        let end = find('E').expect("Didn't find the end position");
+        Maze { grid, start, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_1: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    const SAMPLE_2: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################";
+
+    #[test]
+    fn test_parse() {
+        let maze = parser::parse(SAMPLE_1);
+        assert_eq!(Xy::new(1, 13), maze.start);
+        assert_eq!(Xy::new(13, 1), maze.end);
+        assert!(maze.is_wall(Xy::new(0, 0)));
+        assert!(!maze.is_wall(Xy::new(1, 1)));
+        assert!(maze.is_wall(Xy::new(-1, 1)));
+        assert_eq!(format!("{}\n", SAMPLE_1), maze.to_string());
+    }
+
+    #[test]
+    fn test_solve() {
+        let solution = parser::parse(SAMPLE_1).solve().unwrap();
+        assert_eq!(7036, solution.score);
+        assert_eq!(45, solution.tiles.len());
+
+        let solution = parser::parse(SAMPLE_2).solve().unwrap();
+        assert_eq!(11048, solution.score);
+        assert_eq!(64, solution.tiles.len());
+    }
+
+    #[test]
+    fn test_no_path() {
+        let maze = parser::parse("#####\n#S#E#\n#####");
+        assert_eq!(None, maze.solve());
+    }
+
+    #[test]
+    fn test_render() {
+        let maze = parser::parse(SAMPLE_1);
+        let solution = maze.solve().unwrap();
+        let expected = "###############
+#.......#....O#
+#.#.###.#.###O#
+#.....#.#...#O#
+#.###.#####.#O#
+#.#.#.......#O#
+#.#.#####.###O#
+#..OOOOOOOOO#O#
+###O#O#####O#O#
+#OOO#O....#O#O#
+#O#O#O###.#O#O#
+#OOOOO#...#O#O#
+#O###.#.#.#O#O#
+#O..#.....#OOO#
+###############
+";
+        assert_eq!(expected, maze.render(&solution.tiles));
+    }
+}
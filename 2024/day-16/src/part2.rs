@@ -1,18 +1,35 @@
+use crate::common::*;
 use crate::custom_error::AocError;
 
-#[tracing::instrument(skip(_input))]
-pub fn process(_input: &str) -> miette::Result<String, AocError> {
-    panic!("Part 2 not done");
+#[tracing::instrument(skip(input))]
+pub fn process(input: &str) -> miette::Result<String, AocError> {
+    let maze = parser::parse(input);
+    let seats = maze.solve().map(|e| e.tiles.len()).unwrap_or(0);
+    Ok(seats.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    const MAP: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "";
-        assert_eq!("", process(input)?);
+        assert_eq!("45", process(MAP)?);
         Ok(())
     }
 }
@@ -4,7 +4,7 @@ use std::ops::BitXor;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut device = parser::parse(input);
+    let mut device = parser::parse(input)?;
     while !device.step() {}
     println!("{}", device);
     Ok("".to_string())
@@ -195,18 +195,18 @@ impl Instruction {
 pub mod parser {
     use super::*;
     use nom::{
-        bytes::complete::tag,
         character::complete::{i32, newline},
         multi::separated_list1,
         sequence::{preceded, terminated},
         IResult,
     };
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
 
-    pub fn parse(input: &str) -> Device {
-        let (_input, device) = parse_device(input).unwrap();
-        device
+    pub fn parse(input: &str) -> Result<Device, ParseError> {
+        parse_all(parse_device, input)
     }
-    pub fn parse_device(input: &str) -> IResult<&str, Device> {
+    pub fn parse_device(input: &str) -> IResult<&str, Device, ErrorTree<&str>> {
         let (input, register_a) = terminated(preceded(tag("Register A: "), i32), newline)(input)?;
         let (input, register_b) = terminated(preceded(tag("Register B: "), i32), newline)(input)?;
         let (input, register_c) = terminated(preceded(tag("Register C: "), i32), newline)(input)?;
@@ -243,7 +243,7 @@ Program: 0,1,5,4,3,0";
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        let mut device = parser::parse(SAMPLE_1);
+        let mut device = parser::parse(SAMPLE_1).unwrap();
         while !device.step() {
             println!("{}", device);
         }
@@ -4,7 +4,7 @@ use std::ops::BitXor;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let mut device = parser::parse(input);
+    let mut device = parser::parse(input)?;
     while !device.step() {
         if let Some(o) = device.last_out.take() {
             print!("{},", o);
@@ -14,7 +14,7 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
     Ok("".to_string())
 }
 pub fn process_limit(input: &str, start: i64, end: i64) -> miette::Result<String, AocError> {
-    let mut device = parser::parse(input);
+    let mut device = parser::parse(input)?;
     for i in start..end {
         if i % 10000000 == 0 {
             println!("Trying {}", i);
@@ -249,18 +249,18 @@ impl Instruction {
 pub mod parser {
     use super::*;
     use nom::{
-        bytes::complete::tag,
         character::complete::{i64, newline},
         multi::separated_list1,
         sequence::{preceded, terminated},
         IResult,
     };
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
 
-    pub fn parse(input: &str) -> Device {
-        let (_input, device) = parse_device(input).unwrap();
-        device
+    pub fn parse(input: &str) -> Result<Device, ParseError> {
+        parse_all(parse_device, input)
     }
-    pub fn parse_device(input: &str) -> IResult<&str, Device> {
+    pub fn parse_device(input: &str) -> IResult<&str, Device, ErrorTree<&str>> {
         let (input, register_a) = terminated(preceded(tag("Register A: "), i64), newline)(input)?;
         let (input, register_b) = terminated(preceded(tag("Register B: "), i64), newline)(input)?;
         let (input, register_c) = terminated(preceded(tag("Register C: "), i64), newline)(input)?;
@@ -0,0 +1,198 @@
+use std::collections::{HashSet, VecDeque};
+use utils::dsu::Dsu;
+use utils::grid::{Direction4, Grid, Xy};
+
+pub struct Memory {
+    pub bytes: Vec<Xy>,
+    pub exit: Xy,
+}
+
+impl Memory {
+    pub fn new(bytes: Vec<Xy>, exit: Xy) -> Self {
+        Self { bytes, exit }
+    }
+
+    pub fn in_bounds(&self, xy: Xy) -> bool {
+        (0..=self.exit.x).contains(&xy.x) && (0..=self.exit.y).contains(&xy.y)
+    }
+
+    fn index(&self, xy: Xy) -> usize {
+        (xy.y * (self.exit.x + 1) + xy.x) as usize
+    }
+
+    pub fn grid(&self, limit: usize) -> Grid<char> {
+        let mut grid = Grid::empty();
+        grid.insert(self.exit, '.');
+        for &xy in self.bytes.iter().take(limit) {
+            grid.insert(xy, '#');
+        }
+        grid
+    }
+
+    pub fn shortest_path(&self, limit: usize) -> Option<i32> {
+        let grid = self.grid(limit);
+        let mut visited = HashSet::<Xy>::new();
+        let mut queue = VecDeque::<(Xy, i32)>::new();
+        queue.push_back((Xy::new(0, 0), 0));
+        while let Some((cell, distance)) = queue.pop_front() {
+            if !self.in_bounds(cell) || Some(&'#') == grid.get(cell) {
+                continue;
+            }
+            if cell == self.exit {
+                return Some(distance);
+            }
+            if !visited.insert(cell) {
+                continue;
+            }
+            for d in Direction4::all() {
+                queue.push_back((cell + d, distance + 1));
+            }
+        }
+        None
+    }
+
+    pub fn first_blocker_bisect(&self) -> Option<Xy> {
+        if self.shortest_path(self.bytes.len()).is_some() {
+            return None;
+        }
+        let (mut low, mut high) = (0, self.bytes.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            if self.shortest_path(mid).is_some() {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low.checked_sub(1).map(|i| self.bytes[i])
+    }
+
+    /// Lifts the bytes in reverse until a union-find joins the start and exit.
+    pub fn first_blocker(&self) -> Option<Xy> {
+        let mut fallen = vec![None; self.index(self.exit) + 1];
+        for (i, &xy) in self.bytes.iter().enumerate() {
+            if self.in_bounds(xy) {
+                fallen[self.index(xy)].get_or_insert(i);
+            }
+        }
+        let mut dsu = Dsu::new(fallen.len());
+        for y in 0..=self.exit.y {
+            for x in 0..=self.exit.x {
+                let xy = Xy::new(x, y);
+                if fallen[self.index(xy)].is_none() {
+                    self.join(&mut dsu, &fallen, xy);
+                }
+            }
+        }
+        let (start, exit) = (self.index(Xy::new(0, 0)), self.index(self.exit));
+        if dsu.same(start, exit) {
+            return None;
+        }
+        for (i, &xy) in self.bytes.iter().enumerate().rev() {
+            if !self.in_bounds(xy) || fallen[self.index(xy)] != Some(i) {
+                continue;
+            }
+            fallen[self.index(xy)] = None;
+            self.join(&mut dsu, &fallen, xy);
+            if dsu.same(start, exit) {
+                return Some(xy);
+            }
+        }
+        None
+    }
+
+    fn join(&self, dsu: &mut Dsu, fallen: &[Option<usize>], xy: Xy) {
+        for d in Direction4::all() {
+            let next = xy + d;
+            if self.in_bounds(next) && fallen[self.index(next)].is_none() {
+                dsu.union(self.index(xy), self.index(next));
+            }
+        }
+    }
+}
+
+pub mod parser {
+    use super::*;
+
+    pub fn parse(input: &str) -> Vec<Xy> {
+        input
+            .lines()
+            .map(|line| {
+                let coords = line
+                    .split(",")
+                    .map(|e| e.parse::<i32>().unwrap())
+                    .collect::<Vec<_>>();
+                Xy::new(coords[0], coords[1])
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+    #[test]
+    fn test_parse() {
+        let memory = Memory::new(parser::parse(SAMPLE), Xy::new(6, 6));
+        assert_eq!(25, memory.bytes.len());
+        let grid = memory.grid(12);
+        assert_eq!(7, grid.height());
+        assert_eq!(7, grid.width());
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        let memory = Memory::new(parser::parse(SAMPLE), Xy::new(6, 6));
+        assert_eq!(Some(12), memory.shortest_path(0));
+        assert_eq!(Some(22), memory.shortest_path(12));
+        assert_eq!(None, memory.shortest_path(21));
+    }
+
+    #[test]
+    fn test_first_blocker() {
+        let memory = Memory::new(parser::parse(SAMPLE), Xy::new(6, 6));
+        assert_eq!(Some(Xy::new(6, 1)), memory.first_blocker());
+        assert_eq!(memory.first_blocker(), memory.first_blocker_bisect());
+        for limit in 0..memory.bytes.len() {
+            let memory = Memory::new(memory.bytes[..limit].to_vec(), memory.exit);
+            assert_eq!(memory.first_blocker(), memory.first_blocker_bisect());
+        }
+    }
+
+    #[test]
+    fn test_blocked_exit() {
+        let bytes = vec![Xy::new(1, 0), Xy::new(2, 2), Xy::new(0, 1)];
+        let memory = Memory::new(bytes, Xy::new(2, 2));
+        assert_eq!(Some(4), memory.shortest_path(1));
+        assert_eq!(None, memory.shortest_path(2));
+        assert_eq!(Some(Xy::new(2, 2)), memory.first_blocker());
+        assert_eq!(memory.first_blocker(), memory.first_blocker_bisect());
+    }
+}
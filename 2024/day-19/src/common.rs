@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<char, usize>,
+    towel: Option<usize>,
+}
+
+#[derive(Debug)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    pub fn new(towels: &[String]) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (i, towel) in towels.iter().enumerate() {
+            let mut node = 0;
+            for c in towel.chars() {
+                node = match nodes[node].children.get(&c) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].children.insert(c, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].towel.get_or_insert(i);
+        }
+        Self { nodes }
+    }
+
+    pub fn prefixes<'a>(&'a self, design: &'a str) -> impl Iterator<Item = (usize, usize)> + 'a {
+        design
+            .char_indices()
+            .scan(0, move |node, (i, c)| {
+                *node = *self.nodes[*node].children.get(&c)?;
+                Some((i + c.len_utf8(), self.nodes[*node].towel))
+            })
+            .filter_map(|(len, towel)| towel.map(|e| (len, e)))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Arrangements<'a> {
+    pub count: u64,
+    pub example: Option<Vec<&'a str>>,
+}
+
+impl Arrangements<'_> {
+    pub fn is_possible(&self) -> bool {
+        self.count > 0
+    }
+}
+
+#[derive(Debug)]
+pub struct Matcher {
+    towels: Vec<String>,
+    trie: Trie,
+}
+
+impl Matcher {
+    pub fn new(towels: Vec<String>) -> Self {
+        let trie = Trie::new(&towels);
+        Self { towels, trie }
+    }
+
+    pub fn arrangements(&self, design: &str) -> Arrangements<'_> {
+        let mut ways = vec![0_u64; design.len() + 1];
+        let mut previous: Vec<Option<(usize, usize)>> = vec![None; design.len() + 1];
+        ways[0] = 1;
+        for start in 0..design.len() {
+            if ways[start] == 0 {
+                continue;
+            }
+            for (len, towel) in self.trie.prefixes(&design[start..]) {
+                ways[start + len] += ways[start];
+                previous[start + len].get_or_insert((start, towel));
+            }
+        }
+
+        let count = ways[design.len()];
+        let example = (count > 0).then(|| {
+            let mut example = vec![];
+            let mut end = design.len();
+            while let Some((start, towel)) = previous[end] {
+                example.push(self.towels[towel].as_str());
+                end = start;
+            }
+            example.reverse();
+            example
+        });
+        Arrangements { count, example }
+    }
+}
+
+pub struct Puzzle {
+    pub towels: Vec<String>,
+    pub patterns: Vec<String>,
+}
+
+pub mod parser {
+    use super::*;
+
+    pub fn parse(input: &str) -> Puzzle {
+        let mut lines = input.lines();
+        let towels = lines
+            .next()
+            .unwrap()
+            .split(", ")
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        lines.next();
+        let patterns = lines.map(|e| e.to_string()).collect::<Vec<_>>();
+        Puzzle { towels, patterns }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb";
+
+    #[test]
+    fn test_parse() {
+        let puzzle = parser::parse(SAMPLE);
+        assert_eq!(8, puzzle.towels.len());
+        assert_eq!("wr", puzzle.towels[1]);
+        assert_eq!(8, puzzle.patterns.len());
+        assert_eq!("bggr", puzzle.patterns[1]);
+    }
+
+    #[test]
+    fn test_prefixes() {
+        let puzzle = parser::parse(SAMPLE);
+        let trie = Trie::new(&puzzle.towels);
+        assert_eq!(
+            vec![(1, 2), (2, 7)],
+            trie.prefixes("brwrr").collect::<Vec<_>>()
+        );
+        assert_eq!(0, trie.prefixes("ubwu").count());
+        assert_eq!(
+            vec![(1, 2), (3, 4)],
+            trie.prefixes("bwu").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_arrangements() {
+        let puzzle = parser::parse(SAMPLE);
+        let matcher = Matcher::new(puzzle.towels);
+        let counts = puzzle
+            .patterns
+            .iter()
+            .map(|e| matcher.arrangements(e).count)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![2, 1, 4, 6, 0, 1, 2, 0], counts);
+
+        let arrangements = matcher.arrangements("brwrr");
+        assert_eq!(Some(vec!["br", "wr", "r"]), arrangements.example);
+        assert!(arrangements.is_possible());
+        let arrangements = matcher.arrangements("ubwu");
+        assert_eq!(None, arrangements.example);
+        assert!(!arrangements.is_possible());
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt::Display;
+
+use utils::grid::{Direction4, Grid, Xy};
+
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
+pub enum Item {
+    Empty,
+    Wall,
+}
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Item::Empty => write!(f, ".")?,
+            Item::Wall => write!(f, "#")?,
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Cheats {
+    pub savings: BTreeMap<usize, usize>,
+}
+
+impl Cheats {
+    pub fn count(&self) -> usize {
+        self.savings.values().sum()
+    }
+}
+
+pub struct Map {
+    grid: Grid<Item>,
+    start: Xy,
+    end: Xy,
+}
+
+impl Map {
+    pub fn distances(&self, from: Xy) -> HashMap<Xy, usize> {
+        let mut distances = HashMap::new();
+        let mut queue = VecDeque::new();
+        distances.insert(from, 0);
+        queue.push_back(from);
+        while let Some(current) = queue.pop_front() {
+            let distance = distances[&current];
+            for d in Direction4::all() {
+                let next = current + d;
+                if Some(&Item::Empty) == self.grid.get(next) && !distances.contains_key(&next) {
+                    distances.insert(next, distance + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        distances
+    }
+
+    pub fn shortest_path(&self) -> Option<usize> {
+        self.distances(self.start).get(&self.end).copied()
+    }
+
+    /// Cheats of up to `max_duration` saving at least `min_saving`, counted by saving.
+    pub fn cheats(&self, max_duration: usize, min_saving: usize) -> Cheats {
+        let from_start = self.distances(self.start);
+        let to_end = self.distances(self.end);
+        let mut cheats = Cheats::default();
+        let Some(&honest) = from_start.get(&self.end) else {
+            return cheats;
+        };
+        let radius = max_duration as i32;
+        for (&current, &before) in from_start.iter() {
+            for dy in -radius..=radius {
+                let width = radius - dy.abs();
+                for dx in -width..=width {
+                    let Some(&after) = to_end.get(&(current + Xy::new(dx, dy))) else {
+                        continue;
+                    };
+                    let length = before + (dx.abs() + dy.abs()) as usize + after;
+                    if length + min_saving <= honest {
+                        *cheats.savings.entry(honest - length).or_default() += 1;
+                    }
+                }
+            }
+        }
+        cheats
+    }
+}
+
+pub mod parser {
+    use super::*;
+    pub fn parse(input: &str) -> Map {
+        let mut grid = Grid::empty();
+        let mut start = Xy::new(0, 0);
+        let mut end = Xy::new(0, 0);
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let item = match c {
+                    '#' => Item::Wall,
+                    '.' => Item::Empty,
+                    'S' => {
+                        start = Xy::new(x as i32, y as i32);
+                        Item::Empty
+                    }
+                    'E' => {
+                        end = Xy::new(x as i32, y as i32);
+                        Item::Empty
+                    }
+                    _ => panic!("Bad map"),
+                };
+                grid.insert(Xy::new(x as i32, y as i32), item);
+            }
+        }
+        Map { grid, start, end }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    const SAMPLE: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn test_shortest_path() {
+        let map = parser::parse(SAMPLE);
+        assert_eq!(Some(84), map.shortest_path());
+        assert_eq!(85, map.distances(map.start).len());
+    }
+
+    #[test]
+    fn test_short_cheats() {
+        let map = parser::parse(SAMPLE);
+        let cheats = map.cheats(2, 1);
+        assert_eq!(
+            BTreeMap::from([
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1)
+            ]),
+            cheats.savings
+        );
+        assert_eq!(44, cheats.count());
+    }
+
+    #[test]
+    fn test_long_cheats() {
+        let map = parser::parse(SAMPLE);
+        let cheats = map.cheats(20, 50);
+        assert_eq!(
+            BTreeMap::from([
+                (50, 32),
+                (52, 31),
+                (54, 29),
+                (56, 39),
+                (58, 25),
+                (60, 23),
+                (62, 20),
+                (64, 19),
+                (66, 12),
+                (68, 14),
+                (70, 12),
+                (72, 22),
+                (74, 4),
+                (76, 3)
+            ]),
+            cheats.savings
+        );
+        assert_eq!(285, cheats.count());
+    }
+}
@@ -55,7 +55,7 @@ type Inputs = HashMap<String, bool>;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (mut gates, mut inputs) = parser::parse(input);
+    let (mut gates, mut inputs) = parser::parse(input)?;
     while step(&mut inputs, &mut gates) {}
     let mut zs = inputs
         .iter()
@@ -71,20 +71,24 @@ pub fn process(input: &str) -> miette::Result<String, AocError> {
 mod parser {
     use super::*;
     use nom::{
-        bytes::complete::{tag, take},
-        character::complete::{i32, line_ending, one_of},
+        branch::alt,
+        bytes::complete::take,
+        character::complete::{i32, line_ending},
         combinator::map,
         multi::{many1, separated_list1},
-        sequence::{separated_pair, terminated},
+        sequence::{pair, separated_pair, terminated},
         IResult,
     };
-    pub fn parse(input: &str) -> (Vec<Gate>, Inputs) {
-        let (input, inputs) = parse_inputs(input).unwrap();
-        let (_, gates) = separated_list1(line_ending, gate)(input).unwrap();
-        (gates, inputs.into_iter().collect::<Inputs>())
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
+
+    pub fn parse(input: &str) -> Result<(Vec<Gate>, Inputs), ParseError> {
+        let (inputs, gates) =
+            parse_all(pair(parse_inputs, separated_list1(line_ending, gate)), input)?;
+        Ok((gates, inputs))
     }
 
-    pub fn parse_inputs(input: &str) -> IResult<&str, Inputs> {
+    pub fn parse_inputs(input: &str) -> IResult<&str, Inputs, ErrorTree<&str>> {
         terminated(
             map(
                 many1(separated_pair(
@@ -97,23 +101,21 @@ mod parser {
             line_ending,
         )(input)
     }
-    pub fn gate(input: &str) -> IResult<&str, Gate> {
+    pub fn gate(input: &str) -> IResult<&str, Gate, ErrorTree<&str>> {
         let (input, input1) =
             map(terminated(take(3_usize), tag(" ")), |s: &str| s.to_string())(input)?;
         let (input, operation) = terminated(
-            map(many1(one_of("ANDXOR")), |v| v.iter().collect::<String>()),
+            alt((
+                map(tag("AND"), |_| Operation::AND),
+                map(tag("XOR"), |_| Operation::XOR),
+                map(tag("OR"), |_| Operation::OR),
+            )),
             tag(" "),
         )(input)?;
         let (input, input2) = map(terminated(take(3_usize), tag(" -> ")), |s: &str| {
             s.to_string()
         })(input)?;
         let (input, output) = map(take(3_usize), |s: &str| s.to_string())(input)?;
-        let operation = match &operation[..] {
-            "AND" => Operation::AND,
-            "XOR" => Operation::XOR,
-            "OR" => Operation::OR,
-            _ => panic!("Bad operation"),
-        };
         let gate = Gate {
             input1,
             input2,
@@ -196,7 +198,7 @@ tnw OR pbm -> gnj";
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        let (gates, inputs) = parser::parse(SAMPLE_1);
+        let (gates, inputs) = parser::parse(SAMPLE_1)?;
         assert_eq!(6, inputs.len());
         assert_eq!(3, gates.len());
         Ok(())
@@ -204,7 +206,7 @@ tnw OR pbm -> gnj";
 
     #[test]
     fn test_value() -> miette::Result<()> {
-        let (gates, inputs) = parser::parse(SAMPLE_1);
+        let (gates, inputs) = parser::parse(SAMPLE_1)?;
         assert_eq!(Some(false), gates[0].value(&inputs));
         assert_eq!(Some(false), gates[1].value(&inputs));
         assert_eq!(Some(true), gates[2].value(&inputs));
@@ -213,7 +215,7 @@ tnw OR pbm -> gnj";
 
     #[test]
     fn test_step() -> miette::Result<()> {
-        let (mut gates, mut inputs) = parser::parse(SAMPLE_2);
+        let (mut gates, mut inputs) = parser::parse(SAMPLE_2)?;
         dbg!(&gates[1]);
         assert_eq!(None, gates[1].value);
         assert!(step(&mut inputs, &mut gates));
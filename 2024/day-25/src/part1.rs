@@ -2,7 +2,7 @@ use crate::custom_error::AocError;
 
 #[tracing::instrument(skip(input))]
 pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let (keys, locks) = parser::parse(input);
+    let (keys, locks) = parser::parse(input)?;
 
     let mut result = 0;
     for l in &locks[..] {
@@ -41,14 +41,16 @@ mod parser {
     use super::*;
     use nom::{
         branch::alt,
-        bytes::complete::tag,
         character::complete::{line_ending, one_of},
         multi::{count, separated_list1},
         sequence::terminated,
         IResult,
     };
-    pub fn parse(input: &str) -> (Vec<Item>, Vec<Item>) {
-        let (_input, items) = separated_list1(line_ending, key_or_lock)(input).unwrap();
+    use nom_supreme::{error::ErrorTree, tag::complete::tag};
+    use utils::{error::ParseError, parse::parse_all};
+
+    pub fn parse(input: &str) -> Result<(Vec<Item>, Vec<Item>), ParseError> {
+        let items = parse_all(separated_list1(line_ending, key_or_lock), input)?;
         let mut keys = vec![];
         let mut locks = vec![];
         for i in items {
@@ -57,28 +59,28 @@ mod parser {
                 Item::Lock(_) => locks.push(i),
             }
         }
-        (keys, locks)
+        Ok((keys, locks))
     }
 
-    pub fn key_or_lock(input: &str) -> IResult<&str, Item> {
+    pub fn key_or_lock(input: &str) -> IResult<&str, Item, ErrorTree<&str>> {
         alt((key, lock))(input)
     }
 
-    pub fn key(input: &str) -> IResult<&str, Item> {
+    pub fn key(input: &str) -> IResult<&str, Item, ErrorTree<&str>> {
         let (input, _) = terminated(tag("....."), line_ending)(input)?;
         let (input, pins) = body(input)?;
         let (input, _) = terminated(tag("#####"), line_ending)(input)?;
         Ok((input, Item::Key(pins)))
     }
 
-    pub fn lock(input: &str) -> IResult<&str, Item> {
+    pub fn lock(input: &str) -> IResult<&str, Item, ErrorTree<&str>> {
         let (input, _) = terminated(tag("#####"), line_ending)(input)?;
         let (input, pins) = body(input)?;
         let (input, _) = terminated(tag("....."), line_ending)(input)?;
         Ok((input, Item::Lock(pins)))
     }
 
-    pub fn body(input: &str) -> IResult<&str, [i32; 5]> {
+    pub fn body(input: &str) -> IResult<&str, [i32; 5], ErrorTree<&str>> {
         let (input, lines) = count(terminated(count(one_of(".#"), 5), line_ending), 5)(input)?;
         let mut result = [0, 0, 0, 0, 0];
         for i in 0..5 {
@@ -145,7 +147,7 @@ mod tests {
 
     #[test]
     fn test_parse() -> miette::Result<()> {
-        let (keys, locks) = parser::parse(SAMPLE_1);
+        let (keys, locks) = parser::parse(SAMPLE_1)?;
         assert_eq!(3, keys.len());
         assert_eq!(2, locks.len());
         assert_eq!(Item::Lock([0, 5, 3, 4, 3]), locks[0]);
@@ -153,6 +155,8 @@ mod tests {
         assert!(!locks[0].may_fit(&keys[0]));
         assert!(!locks[0].may_fit(&keys[1]));
         assert!(locks[0].may_fit(&keys[2]));
+        let err = parser::parse(&SAMPLE_1.replacen(".####", ".#x##", 1)).unwrap_err();
+        assert_eq!((2, 3), (err.line, err.column));
         Ok(())
     }
 }
@@ -0,0 +1,176 @@
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr};
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+fn segments(format: &str) -> Result<Vec<Segment>, String> {
+    let mut result = vec![];
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder {{{}", name)),
+                    }
+                }
+                if !literal.is_empty() {
+                    result.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                result.push(Segment::Field(name.trim().to_string()));
+            }
+            '}' => return Err("unmatched } in format".to_string()),
+            c => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        result.push(Segment::Literal(literal));
+    }
+    Ok(result)
+}
+
+#[proc_macro_derive(AocParse, attributes(aoc))]
+pub fn derive_aoc_parse(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn expand(input: DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(
+            name,
+            "AocParse can only be derived for structs",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            name,
+            "AocParse needs a struct with named fields",
+        ));
+    };
+    let attr = input
+        .attrs
+        .iter()
+        .find(|e| e.path().is_ident("aoc"))
+        .ok_or_else(|| Error::new_spanned(name, "missing #[aoc(\"...\")] format"))?;
+    let format: LitStr = attr.parse_args()?;
+    let segments = segments(&format.value()).map_err(|e| Error::new_spanned(&format, e))?;
+
+    let mut steps = vec![];
+    let mut seen = vec![];
+    for segment in &segments {
+        match segment {
+            Segment::Literal(text) => steps.push(quote! {
+                let (input, _) = tag(#text)(input)?;
+            }),
+            Segment::Field(field) => {
+                let Some(f) = fields
+                    .named
+                    .iter()
+                    .find(|e| e.ident.as_ref().is_some_and(|e| e == field))
+                else {
+                    return Err(Error::new_spanned(
+                        &format,
+                        format!("{} has no field named {}", name, field),
+                    ));
+                };
+                if seen.contains(field) {
+                    return Err(Error::new_spanned(
+                        &format,
+                        format!("field {} appears more than once", field),
+                    ));
+                }
+                seen.push(field.clone());
+                let ident = Ident::new(field, Span::call_site());
+                let ty = &f.ty;
+                steps.push(quote! {
+                    let (input, #ident) = <#ty as ::utils::parse::AocParse>::parse_nom(input)?;
+                });
+            }
+        }
+    }
+    if let Some(missing) = fields
+        .named
+        .iter()
+        .filter_map(|e| e.ident.as_ref())
+        .find(|e| !seen.contains(&e.to_string()))
+    {
+        return Err(Error::new_spanned(
+            &format,
+            format!("field {} does not appear in the format", missing),
+        ));
+    }
+
+    let field_names = seen
+        .iter()
+        .map(|e| Ident::new(e, Span::call_site()))
+        .collect::<Vec<_>>();
+    let context = name.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::utils::parse::AocParse for #name #ty_generics #where_clause {
+            fn parse_nom(
+                input: &str,
+            ) -> ::utils::__private::nom::IResult<
+                &str,
+                Self,
+                ::utils::__private::nom_supreme::error::ErrorTree<&str>,
+            > {
+                use ::utils::__private::nom_supreme::tag::complete::tag;
+                ::utils::__private::in_context(#context, |input| {
+                    #(#steps)*
+                    Ok((input, Self { #(#field_names),* }))
+                })(input)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            Ok(vec![
+                Segment::Literal("p=".to_string()),
+                Segment::Field("x".to_string()),
+                Segment::Literal(",".to_string()),
+                Segment::Field("y".to_string()),
+            ]),
+            segments("p={x},{ y }")
+        );
+        assert_eq!(
+            Ok(vec![
+                Segment::Literal("{".to_string()),
+                Segment::Field("a".to_string()),
+                Segment::Literal("}".to_string()),
+            ]),
+            segments("{{{a}}}")
+        );
+        assert!(segments("p={x").is_err());
+        assert!(segments("p=}").is_err());
+    }
+}
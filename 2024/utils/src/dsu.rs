@@ -0,0 +1,75 @@
+/// Union-find over `0..len` with path halving and union by size.
+#[derive(Debug, Clone)]
+pub struct Dsu {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl Dsu {
+    pub fn new(len: usize) -> Self {
+        Self {
+            parent: (0..len).collect(),
+            size: vec![1; len],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Returns false if `a` and `b` were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    pub fn size(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union() {
+        let mut dsu = Dsu::new(6);
+        assert_eq!(6, dsu.len());
+        assert!(!dsu.same(0, 1));
+        assert!(dsu.union(0, 1));
+        assert!(dsu.union(2, 3));
+        assert!(!dsu.union(1, 0));
+        assert!(dsu.same(1, 0));
+        assert!(!dsu.same(1, 2));
+        assert!(dsu.union(3, 1));
+        assert!(dsu.same(0, 2));
+        assert_eq!(4, dsu.size(2));
+        assert_eq!(1, dsu.size(5));
+    }
+}
@@ -0,0 +1,211 @@
+use std::fmt::{Debug, Display};
+
+use miette::{Diagnostic, SourceSpan};
+use nom::Offset;
+use nom_locate::LocatedSpan;
+use nom_supreme::error::{BaseErrorKind, GenericErrorTree, StackContext};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug, Clone, PartialEq, Eq)]
+#[error("parse error at line {line}, column {column}: expected {expected}")]
+#[diagnostic(code(aoc::parse_error))]
+pub struct ParseError {
+    #[source_code]
+    pub snippet: String,
+    #[label("expected {expected}")]
+    pub span: SourceSpan,
+    #[help]
+    pub context: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |e| e + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |e| offset + e);
+        let snippet = input[line_start..line_end].trim_end_matches('\r');
+        let len = input[offset..line_end.max(offset)]
+            .chars()
+            .next()
+            .map_or(0, |c| c.len_utf8());
+        Self {
+            snippet: snippet.to_string(),
+            span: (offset - line_start, len).into(),
+            context: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            expected: expected.into(),
+        }
+    }
+
+    pub fn with_context(mut self, context: &[String]) -> Self {
+        if !context.is_empty() {
+            self.context = Some(format!("while parsing {}", context.join(" in ")));
+        }
+        self
+    }
+
+    pub fn from_nom<E: Located>(input: &str, err: nom::Err<E>) -> Self {
+        match err {
+            nom::Err::Incomplete(_) => Self::new(input, input.len(), "more input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let failure = e.locate(input);
+                Self::new(input, failure.offset, failure.expected.join(" or "))
+                    .with_context(&failure.context)
+            }
+        }
+    }
+}
+
+pub trait Position {
+    fn offset_in(&self, input: &str) -> usize;
+}
+
+impl Position for &str {
+    fn offset_in(&self, input: &str) -> usize {
+        input.offset(self)
+    }
+}
+
+impl<X> Position for LocatedSpan<&str, X> {
+    fn offset_in(&self, _input: &str) -> usize {
+        self.location_offset()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub offset: usize,
+    pub expected: Vec<String>,
+    pub context: Vec<String>,
+}
+
+pub trait Located {
+    fn locate(&self, input: &str) -> Failure;
+}
+
+impl<I: Position> Located for nom::error::Error<I> {
+    fn locate(&self, input: &str) -> Failure {
+        Failure {
+            offset: self.input.offset_in(input),
+            expected: vec![self.code.description().to_string()],
+            context: vec![],
+        }
+    }
+}
+
+impl<I: Position, T: Debug, C: Debug + Display, E: Display> Located
+    for GenericErrorTree<I, T, C, E>
+{
+    fn locate(&self, input: &str) -> Failure {
+        match self {
+            Self::Base { location, kind } => Failure {
+                offset: location.offset_in(input),
+                expected: vec![match kind {
+                    BaseErrorKind::Expected(e) => e.to_string(),
+                    BaseErrorKind::Kind(k) => k.description().to_string(),
+                    BaseErrorKind::External(e) => e.to_string(),
+                }],
+                context: vec![],
+            },
+            Self::Stack { base, contexts } => {
+                let mut failure = base.locate(input);
+                failure
+                    .context
+                    .extend(contexts.iter().filter_map(|(_, c)| match c {
+                        StackContext::Context(c) => Some(c.to_string()),
+                        StackContext::Kind(_) => None,
+                    }));
+                failure
+            }
+            Self::Alt(siblings) => {
+                let failures = siblings.iter().map(|e| e.locate(input)).collect::<Vec<_>>();
+                let furthest = failures.iter().map(|e| e.offset).max().unwrap_or(0);
+                let mut result = Failure {
+                    offset: furthest,
+                    expected: vec![],
+                    context: vec![],
+                };
+                for failure in failures.into_iter().filter(|e| e.offset == furthest) {
+                    result.expected.extend(failure.expected);
+                    if result.context.is_empty() {
+                        result.context = failure.context;
+                    }
+                }
+                result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::{
+        bytes::complete::tag, character::complete::i32, sequence::separated_pair, IResult, Parser,
+    };
+    use nom_supreme::{error::ErrorTree, ParserExt};
+
+    #[test]
+    fn test_new() {
+        let input = "1,2\n3;4\n";
+        let err = ParseError::new(input, 5, "\",\"");
+        assert_eq!(2, err.line);
+        assert_eq!(2, err.column);
+        assert_eq!("3;4", err.snippet);
+        assert_eq!(SourceSpan::from((1, 1)), err.span);
+        assert_eq!(
+            "parse error at line 2, column 2: expected \",\"",
+            err.to_string()
+        );
+
+        let err = ParseError::new(input, input.len(), "a digit");
+        assert_eq!(3, err.line);
+        assert_eq!(1, err.column);
+        assert_eq!("", err.snippet);
+    }
+
+    #[test]
+    fn test_from_nom_error() {
+        fn pair(input: &str) -> IResult<&str, (i32, i32)> {
+            separated_pair(i32, tag(","), i32)(input)
+        }
+        let input = "12;4";
+        let err = ParseError::from_nom(input, pair(input).unwrap_err());
+        assert_eq!(1, err.line);
+        assert_eq!(3, err.column);
+        assert_eq!("Tag", err.expected);
+    }
+
+    #[test]
+    fn test_from_error_tree() {
+        fn pair(input: &str) -> IResult<&str, (i32, i32), ErrorTree<&str>> {
+            separated_pair(i32, tag(","), i32)
+                .context("pair")
+                .parse(input)
+        }
+        let input = "1,2\n12,x";
+        let err = ParseError::from_nom(input, pair(&input[4..]).unwrap_err());
+        assert_eq!(2, err.line);
+        assert_eq!(4, err.column);
+        assert_eq!("an ascii digit", err.expected);
+        assert_eq!(Some("while parsing pair".to_string()), err.context);
+        assert_eq!("12,x", err.snippet);
+    }
+
+    #[test]
+    fn test_render() {
+        let err = ParseError::new("1,2\n3;4", 5, "\",\"");
+        let mut out = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(&mut out, &err)
+            .unwrap();
+        assert!(out.contains("3;4"));
+        assert!(out.contains("expected \",\""));
+    }
+}
@@ -4,6 +4,8 @@ use std::rc::Rc;
 
 use itertools::Itertools;
 
+use crate::parse::AocParse;
+
 #[derive(Eq, PartialEq, Clone)]
 pub struct Path<T>(T, Option<Rc<Path<T>>>);
 impl<T> Path<T> {
@@ -74,7 +76,7 @@ where
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub enum Direction4 {
     N,
     E,
@@ -198,7 +200,8 @@ impl Direction8 {
         }
     }
 }
-#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd, AocParse)]
+#[aoc("{x},{y}")]
 pub struct Xy {
     pub x: i32,
     pub y: i32,
@@ -1 +1,27 @@
+extern crate self as utils;
+
+pub mod dsu;
+pub mod error;
 pub mod grid;
+pub mod math;
+pub mod parse;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use nom;
+    pub use nom_supreme;
+
+    use nom::{IResult, Parser};
+    use nom_supreme::{ParserExt, error::ErrorTree};
+
+    pub fn in_context<'a, O, F>(
+        context: &'static str,
+        parser: F,
+    ) -> impl FnMut(&'a str) -> IResult<&'a str, O, ErrorTree<&'a str>>
+    where
+        F: FnMut(&'a str) -> IResult<&'a str, O, ErrorTree<&'a str>>,
+    {
+        let mut parser = parser.context(context);
+        move |input| parser.parse(input)
+    }
+}
@@ -0,0 +1,100 @@
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+pub trait Signed:
+    Copy
+    + PartialOrd
+    + From<i8>
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Neg<Output = Self>
+{
+}
+
+impl<T> Signed for T where
+    T: Copy
+        + PartialOrd
+        + From<i8>
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + Neg<Output = T>
+{
+}
+
+fn abs<T: Signed>(a: T) -> T {
+    if a < T::from(0) { -a } else { a }
+}
+
+fn signum<T: Signed>(a: T) -> T {
+    let zero = T::from(0);
+    T::from(if a > zero {
+        1
+    } else if a < zero {
+        -1
+    } else {
+        0
+    })
+}
+
+pub fn gcd<T: Signed>(a: T, b: T) -> T {
+    if b == T::from(0) {
+        abs(a)
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn ext_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    if b == T::from(0) {
+        (abs(a), signum(a), T::from(0))
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Smallest non-negative `t` with `t = a (mod m)` and `t = b (mod n)`, and the combined modulus.
+pub fn crt<T: Signed>(a: T, m: T, b: T, n: T) -> Option<(T, T)> {
+    let zero = T::from(0);
+    let (g, p, _) = ext_gcd(m, n);
+    if (b - a) % g != zero {
+        return None;
+    }
+    let lcm = m / g * n;
+    let k = rem_euclid((b - a) / g * p, n / g);
+    Some((rem_euclid(a + m * k, lcm), lcm))
+}
+
+fn rem_euclid<T: Signed>(a: T, m: T) -> T {
+    let r = a % m;
+    if r < T::from(0) { r + abs(m) } else { r }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(6, gcd(-12i64, 18));
+        assert_eq!(5, gcd(0i128, -5));
+        let (g, x, y) = ext_gcd(240i64, 46);
+        assert_eq!(2, g);
+        assert_eq!(g, 240 * x + 46 * y);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(
+            Some((23, 105)),
+            crt(2i64, 3, 3, 5).and_then(|(t, m)| crt(t, m, 2, 7))
+        );
+        assert_eq!(Some((10, 12)), crt(4i64, 6, 2, 4));
+        assert_eq!(None, crt(1i64, 6, 2, 4));
+    }
+}
@@ -0,0 +1,272 @@
+use std::ops::{Range, RangeFrom, RangeTo};
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1, line_ending, multispace0, one_of, space0, space1},
+    combinator::{map_opt, opt, recognize},
+    error::ParseError as NomParseError,
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair, tuple},
+    Compare, IResult, InputIter, InputLength, InputTake, InputTakeAtPosition, Offset, ParseTo,
+    Parser, Slice,
+};
+use nom_locate::LocatedSpan;
+use nom_supreme::error::ErrorTree;
+
+use crate::error::{ParseError, Position};
+
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+pub trait Input:
+    Clone
+    + Offset
+    + InputLength
+    + InputTake
+    + InputIter<Item = char>
+    + InputTakeAtPosition<Item = char>
+    + Slice<Range<usize>>
+    + Slice<RangeFrom<usize>>
+    + Slice<RangeTo<usize>>
+    + Compare<&'static str>
+{
+}
+
+impl<T> Input for T where
+    T: Clone
+        + Offset
+        + InputLength
+        + InputTake
+        + InputIter<Item = char>
+        + InputTakeAtPosition<Item = char>
+        + Slice<Range<usize>>
+        + Slice<RangeFrom<usize>>
+        + Slice<RangeTo<usize>>
+        + Compare<&'static str>
+{
+}
+
+pub use utils_derive::AocParse;
+
+pub trait AocParse: Sized {
+    fn parse_nom(input: &str) -> IResult<&str, Self, ErrorTree<&str>>;
+
+    fn from_input(input: &str) -> Result<Self, ParseError> {
+        parse_all(Self::parse_nom, input)
+    }
+}
+
+pub trait Integer: FromStr {}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {}
+
+            impl AocParse for $t {
+                fn parse_nom(input: &str) -> IResult<&str, Self, ErrorTree<&str>> {
+                    number(input)
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+pub fn number<I, O, E>(input: I) -> IResult<I, O, E>
+where
+    I: Input + ParseTo<O>,
+    O: Integer,
+    E: NomParseError<I>,
+{
+    map_opt(recognize(pair(opt(one_of("+-")), digit1)), |e: I| {
+        e.parse_to()
+    })(input)
+}
+
+pub fn space_separated_numbers<I, O, E>(input: I) -> IResult<I, Vec<O>, E>
+where
+    I: Input + ParseTo<O>,
+    O: Integer,
+    E: NomParseError<I>,
+{
+    preceded(space0, separated_list1(space1, number))(input)
+}
+
+pub fn comma_separated_numbers<I, O, E>(input: I) -> IResult<I, Vec<O>, E>
+where
+    I: Input + ParseTo<O>,
+    O: Integer,
+    E: NomParseError<I>,
+{
+    separated_list1(tuple((space0, char(','), space0)), number)(input)
+}
+
+pub fn lines<I, O, E, F>(parser: F) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+where
+    I: Input,
+    E: NomParseError<I>,
+    F: Parser<I, O, E>,
+{
+    separated_list1(line_ending, parser)
+}
+
+pub fn blocks<I, O, E, F>(parser: F) -> impl FnMut(I) -> IResult<I, Vec<O>, E>
+where
+    I: Input,
+    E: NomParseError<I>,
+    F: Parser<I, O, E>,
+{
+    separated_list1(pair(line_ending, many1(line_ending)), parser)
+}
+
+pub fn key_value<I, K, V, E, F, G>(key: F, value: G) -> impl FnMut(I) -> IResult<I, (K, V), E>
+where
+    I: Input,
+    E: NomParseError<I>,
+    F: Parser<I, K, E>,
+    G: Parser<I, V, E>,
+{
+    separated_pair(key, tuple((space0, char(':'), space0)), value)
+}
+
+pub fn parse_all<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, ErrorTree<&'a str>>,
+{
+    run(parser, input, input)
+}
+
+pub fn parse_all_located<'a, O, P>(parser: P, input: &'a str) -> Result<O, ParseError>
+where
+    P: Parser<Span<'a>, O, ErrorTree<Span<'a>>>,
+{
+    run(parser, Span::new(input), input)
+}
+
+fn run<I, O, P>(mut parser: P, span: I, input: &str) -> Result<O, ParseError>
+where
+    I: Input + Position,
+    P: Parser<I, O, ErrorTree<I>>,
+{
+    let (rest, value) = parser
+        .parse(span)
+        .map_err(|e| ParseError::from_nom(input, e))?;
+    let (rest, _) =
+        multispace0::<I, ErrorTree<I>>(rest).map_err(|e| ParseError::from_nom(input, e))?;
+    if rest.input_len() > 0 {
+        return Err(ParseError::new(
+            input,
+            rest.offset_in(input),
+            "end of input",
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Xy;
+    use nom::character::complete::alpha1;
+
+    #[derive(Debug, PartialEq, AocParse)]
+    #[aoc("p={pos} v={dx},{dy}")]
+    struct Robot {
+        pos: Xy,
+        dx: i64,
+        dy: i64,
+    }
+
+    type Parsed<'a, O> = IResult<&'a str, O>;
+
+    fn numbered_line(input: Span) -> IResult<Span, (u32, Vec<i32>), ErrorTree<Span>> {
+        let line = input.location_line();
+        let (input, numbers) = comma_separated_numbers(input)?;
+        Ok((input, (line, numbers)))
+    }
+
+    #[test]
+    fn test_number() {
+        let result: Parsed<i64> = number("-20 x");
+        assert_eq!(Ok((" x", -20)), result);
+        let result: Parsed<u8> = number("+7");
+        assert_eq!(Ok(("", 7)), result);
+        assert!(number::<_, u8, nom::error::Error<&str>>("-7").is_err());
+        assert!(number::<_, u8, nom::error::Error<&str>>("256").is_err());
+    }
+
+    #[test]
+    fn test_number_lists() {
+        let result: Parsed<Vec<u64>> = space_separated_numbers("1 20  3");
+        assert_eq!(Ok(("", vec![1, 20, 3])), result);
+        let result: Parsed<Vec<i32>> = space_separated_numbers(" 1 -2");
+        assert_eq!(Ok(("", vec![1, -2])), result);
+        let result: Parsed<Vec<i32>> = space_separated_numbers("1-2");
+        assert_eq!(Ok(("-2", vec![1])), result);
+        let err = parse_all(space_separated_numbers::<_, i32, _>, "1-2").unwrap_err();
+        assert_eq!(2, err.column);
+        let result: Parsed<Vec<i32>> = comma_separated_numbers("1,-20, 3;");
+        assert_eq!(Ok((";", vec![1, -20, 3])), result);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let input = "1 2\n3\n\n4\n\n\n5 6";
+        let result = parse_all(blocks(lines(space_separated_numbers::<_, u32, _>)), input);
+        assert_eq!(
+            vec![vec![vec![1, 2], vec![3]], vec![vec![4]], vec![vec![5, 6]]],
+            result.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_value() {
+        let result = parse_all(
+            lines(key_value(alpha1, number::<_, i32, _>)),
+            "a: 1\nbc :-2\n",
+        );
+        assert_eq!(vec![("a", 1), ("bc", -2)], result.unwrap());
+    }
+
+    #[test]
+    fn test_parse_all_trailing() {
+        let err = parse_all(lines(comma_separated_numbers::<_, i32, _>), "1,2\n3,x\n").unwrap_err();
+        assert_eq!(2, err.line);
+        assert_eq!(2, err.column);
+        assert_eq!("end of input", err.expected);
+    }
+
+    #[test]
+    fn test_located() {
+        let result = parse_all_located(lines(numbered_line), "1,2\n3");
+        assert_eq!(vec![(1, vec![1, 2]), (2, vec![3])], result.unwrap());
+
+        let err = parse_all_located(lines(number::<_, u32, _>), "1\n2\n-3").unwrap_err();
+        assert_eq!(3, err.line);
+        assert_eq!(1, err.column);
+    }
+
+    #[test]
+    fn test_derive() {
+        assert_eq!(Xy::new(3, -4), Xy::from_input("3,-4").unwrap());
+        let robots = parse_all(lines(Robot::parse_nom), "p=0,4 v=3,-3\np=6,3 v=-1,-3\n").unwrap();
+        assert_eq!(
+            Robot {
+                pos: Xy::new(6, 3),
+                dx: -1,
+                dy: -3
+            },
+            robots[1]
+        );
+    }
+
+    #[test]
+    fn test_derive_error() {
+        let err = Robot::from_input("p=0,4 w=3,-3").unwrap_err();
+        assert_eq!(1, err.line);
+        assert_eq!(6, err.column);
+        assert_eq!("\" v=\"", err.expected);
+        assert_eq!(Some("while parsing Robot".to_string()), err.context);
+    }
+}